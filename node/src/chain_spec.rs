@@ -1,12 +1,16 @@
+use std::path::Path;
+
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, BalancesConfig, BlockNumber, GenesisConfig, GrandpaConfig,
+	PoeModuleConfig, Runtime, Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
+use pallet_poe::GenesisClaim;
 use sc_service::ChainType;
+use serde::Deserialize;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{hexdisplay::HexDisplay, sr25519, Bytes, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
-use sp_runtime::traits::{IdentifyAccount, Verify};
+use sp_runtime::traits::{Get, IdentifyAccount, Verify};
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
@@ -36,7 +40,63 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
 }
 
+/// A claim with its owner and the block it was made in.
+pub type GenesisClaimOf = (GenesisClaim, AccountId, BlockNumber);
+
+/// An entry of a genesis claims file.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct GenesisClaimEntry {
+	/// Raw claim bytes, or a digest if `algorithm` is set.
+	claim: Bytes,
+	/// blake2-256, sha2-256 or keccak-256 for a digest claim.
+	algorithm: Option<String>,
+	owner: AccountId,
+	block: BlockNumber,
+}
+
+/// Loads genesis claims from a JSON file holding a list of
+/// `{ "claim": "0x..", "algorithm": "sha2-256", "owner": "5..", "block": 1 }`, where
+/// `algorithm` is left out for raw claims. Building genesis fails if an owner cannot afford the
+/// claim deposit.
+pub fn load_genesis_claims(path: &Path) -> Result<Vec<GenesisClaimOf>, String> {
+	let json = std::fs::read(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+	let entries: Vec<GenesisClaimEntry> = serde_json::from_slice(&json)
+		.map_err(|e| format!("invalid claims file {}: {}", path.display(), e))?;
+
+	let max_claim_length = <Runtime as pallet_poe::Config>::MaxclaimLength::get() as usize;
+	entries
+		.into_iter()
+		.map(|entry| {
+			let hex = format!("0x{}", HexDisplay::from(&entry.claim.0));
+			let claim = match entry.algorithm {
+				Some(name) => {
+					let algorithm = crate::poe_command::parse_algorithm(&name)?;
+					if entry.claim.len() != algorithm.digest_len() as usize {
+						return Err(format!("{} is not a {} digest", hex, name))
+					}
+					GenesisClaim::Digest(algorithm, entry.claim.0)
+				},
+				None => {
+					if entry.claim.len() > max_claim_length {
+						return Err(format!("{} is longer than {} bytes", hex, max_claim_length))
+					}
+					GenesisClaim::Raw(entry.claim.0)
+				},
+			};
+			Ok((claim, entry.owner, entry.block))
+		})
+		.collect()
+}
+
 pub fn development_config() -> Result<ChainSpec, String> {
+	development_config_with_claims(Vec::new())
+}
+
+/// The development chain with `genesis_claims` made at genesis.
+pub fn development_config_with_claims(
+	genesis_claims: Vec<GenesisClaimOf>,
+) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
@@ -59,6 +119,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				genesis_claims.clone(),
 				true,
 			)
 		},
@@ -77,6 +138,13 @@ pub fn development_config() -> Result<ChainSpec, String> {
 }
 
 pub fn local_testnet_config() -> Result<ChainSpec, String> {
+	local_testnet_config_with_claims(Vec::new())
+}
+
+/// The local testnet with `genesis_claims` made at genesis.
+pub fn local_testnet_config_with_claims(
+	genesis_claims: Vec<GenesisClaimOf>,
+) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
@@ -107,6 +175,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				genesis_claims.clone(),
 				true,
 			)
 		},
@@ -130,6 +199,7 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	genesis_claims: Vec<GenesisClaimOf>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -152,5 +222,6 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		poe_module: PoeModuleConfig { claims: genesis_claims },
 	}
 }
@@ -50,4 +50,16 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Hash a local file and print the call data of a digest claim on it.
+	HashClaim(crate::poe_command::HashClaimCmd),
+
+	/// Build the Merkle tree of a directory of files and print the batch anchoring data.
+	MerkleBatch(crate::poe_command::MerkleBatchCmd),
+
+	/// Build a chain specification with claims made at genesis.
+	BuildSpecWithClaims(crate::poe_command::BuildSpecWithClaimsCmd),
+
+	/// Check a claim certificate offline against a finalized header.
+	VerifyProof(crate::poe_command::VerifyProofCmd),
 }
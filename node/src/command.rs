@@ -167,6 +167,10 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run::<Block>(&config))
 		},
+		Some(Subcommand::HashClaim(cmd)) => cmd.run(),
+		Some(Subcommand::MerkleBatch(cmd)) => cmd.run(),
+		Some(Subcommand::BuildSpecWithClaims(cmd)) => cmd.run(),
+		Some(Subcommand::VerifyProof(cmd)) => cmd.run(),
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
 			runner.run_node_until_exit(|config| async move {
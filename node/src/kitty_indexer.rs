@@ -0,0 +1,485 @@
+//! Local index of `pallet_kitty` events.
+//!
+//! A background task decodes the kitty events of every block and serves them over RPC
+//! through the `kitty_` namespace. Events of finalized blocks are persisted in the client's
+//! aux storage and loaded again on startup, then the finalized blocks since the last run are
+//! read straight from the chain. This also covers blocks imported during a major sync, which
+//! are not announced as imports. Blocks on the best chain above the finalized one are only
+//! kept in memory, so a reorg can retract them. Only the latest [`MAX_ENTRIES`] events are
+//! kept, and blocks whose state was pruned before they could be indexed are skipped.
+
+use std::{
+	collections::{BTreeMap, BTreeSet, HashMap},
+	sync::{Arc, RwLock},
+};
+
+use codec::{Decode, Encode};
+use futures::{future::Either, StreamExt};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use node_template_runtime::{AccountId, Balance, BlockNumber, Event, Hash};
+use sc_client_api::{backend::AuxStore, BlockchainEvents, StorageProvider};
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::generic::BlockId;
+
+use crate::service::FullClient;
+
+type KittyEvent = pallet_kitty::Event<node_template_runtime::Runtime>;
+
+/// Number of events kept in the index. Older events are dropped first.
+pub const MAX_ENTRIES: usize = 100_000;
+
+/// Number of persisted events stored under a single aux storage key.
+const PAGE_SIZE: usize = 1_000;
+
+/// Number of finalized blocks indexed between two writes to aux storage while catching up.
+const CATCH_UP_BATCH: BlockNumber = 1_000;
+
+/// Aux storage key of the [`StoreMeta`].
+const META_KEY: &[u8] = b"kitty_index_meta";
+
+/// What happened to a kitty. Fields mirror the named fields of `pallet_kitty::Event`.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, Encode, Decode, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum KittyActivity {
+	/// A kitty was created.
+	Created { owner: AccountId, dna: [u8; 16], gender: String },
+	/// A kitty was bred from two parents.
+	Bred { owner: AccountId, dna: [u8; 16], gender: String, parents: (Hash, Hash) },
+	/// The asking price was changed.
+	PriceSet { owner: AccountId, price: Option<Balance> },
+	/// The kitty changed hands without payment.
+	Transferred { from: AccountId, to: AccountId },
+	/// The kitty was moved by the force origin.
+	ForceTransferred { from: AccountId, to: AccountId },
+	/// The kitty was sold.
+	Bought { buyer: AccountId, seller: AccountId, price: Balance },
+	/// The kitty was locked into a vault and split into shares.
+	Fractionalized { owner: AccountId, asset_id: u32, shares: Balance },
+	/// The kitty was redeemed from its vault.
+	Redeemed { owner: AccountId },
+	/// The kitty was bought out of its vault.
+	BoughtOut { buyer: AccountId, price: Balance },
+	/// A shareholder claimed buyout proceeds.
+	ProceedsClaimed { who: AccountId, shares: Balance, proceeds: Balance },
+	/// The kitty was added to or removed from the blacklist.
+	BlacklistSet { blacklisted: bool },
+}
+
+impl KittyActivity {
+	/// Splits a runtime event into the kitty it concerns and the activity. Events that are
+	/// not about a single kitty yield `None`.
+	fn from_event(event: KittyEvent) -> Option<(Hash, Self)> {
+		Some(match event {
+			KittyEvent::Created { owner, kitty_id, dna, gender } =>
+				(kitty_id, Self::Created { owner, dna, gender: format!("{:?}", gender) }),
+			KittyEvent::Bred { owner, kitty_id, dna, gender, parents } =>
+				(kitty_id, Self::Bred { owner, dna, gender: format!("{:?}", gender), parents }),
+			KittyEvent::PriceSet { owner, kitty_id, price } =>
+				(kitty_id, Self::PriceSet { owner, price }),
+			KittyEvent::Transferred { from, to, kitty_id } =>
+				(kitty_id, Self::Transferred { from, to }),
+			KittyEvent::ForceTransferred { from, to, kitty_id } =>
+				(kitty_id, Self::ForceTransferred { from, to }),
+			KittyEvent::Bought { buyer, seller, kitty_id, price } =>
+				(kitty_id, Self::Bought { buyer, seller, price }),
+			KittyEvent::Fractionalized { owner, kitty_id, asset_id, shares } =>
+				(kitty_id, Self::Fractionalized { owner, asset_id, shares }),
+			KittyEvent::Redeemed { owner, kitty_id } => (kitty_id, Self::Redeemed { owner }),
+			KittyEvent::BoughtOut { buyer, kitty_id, price } =>
+				(kitty_id, Self::BoughtOut { buyer, price }),
+			KittyEvent::ProceedsClaimed { who, kitty_id, shares, proceeds } =>
+				(kitty_id, Self::ProceedsClaimed { who, shares, proceeds }),
+			KittyEvent::BlacklistSet { kitty_id, blacklisted } =>
+				(kitty_id, Self::BlacklistSet { blacklisted }),
+			_ => return None,
+		})
+	}
+
+	/// The accounts that took part in this activity.
+	fn accounts(&self) -> Vec<&AccountId> {
+		match self {
+			Self::Created { owner, .. } |
+			Self::Bred { owner, .. } |
+			Self::PriceSet { owner, .. } |
+			Self::Fractionalized { owner, .. } |
+			Self::Redeemed { owner } => vec![owner],
+			Self::Transferred { from, to } | Self::ForceTransferred { from, to } => vec![from, to],
+			Self::Bought { buyer, seller, .. } => vec![buyer, seller],
+			Self::BoughtOut { buyer, .. } => vec![buyer],
+			Self::ProceedsClaimed { who, .. } => vec![who],
+			Self::BlacklistSet { .. } => Vec::new(),
+		}
+	}
+}
+
+/// A single indexed kitty event.
+#[derive(Clone, Debug, Encode, Decode, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KittyHistoryEntry {
+	/// Number of the block that emitted the event.
+	pub block_number: BlockNumber,
+	/// Hash of the block that emitted the event.
+	pub block_hash: Hash,
+	/// Position of the event in the block's event list.
+	pub event_index: u32,
+	/// The kitty the event is about.
+	pub kitty_id: Hash,
+	/// What happened.
+	pub activity: KittyActivity,
+}
+
+/// Indexed events by insertion order, with lookups by kitty, account and block. Events are
+/// inserted in chain order, so insertion order is also the order they happened in.
+#[derive(Default)]
+struct Entries {
+	next: u64,
+	entries: BTreeMap<u64, KittyHistoryEntry>,
+	by_kitty: HashMap<Hash, BTreeSet<u64>>,
+	by_account: HashMap<AccountId, BTreeSet<u64>>,
+	by_block: HashMap<Hash, Vec<u64>>,
+}
+
+impl Entries {
+	fn get(&self, positions: Option<&BTreeSet<u64>>) -> Vec<KittyHistoryEntry> {
+		positions
+			.into_iter()
+			.flatten()
+			.filter_map(|position| self.entries.get(position))
+			.cloned()
+			.collect()
+	}
+
+	fn push(&mut self, entry: KittyHistoryEntry) {
+		let position = self.next;
+		self.next += 1;
+		self.by_kitty.entry(entry.kitty_id).or_default().insert(position);
+		for account in entry.activity.accounts() {
+			self.by_account.entry(account.clone()).or_default().insert(position);
+		}
+		self.by_block.entry(entry.block_hash).or_default().push(position);
+		self.entries.insert(position, entry);
+	}
+
+	fn remove(&mut self, position: u64) {
+		let entry = match self.entries.remove(&position) {
+			Some(entry) => entry,
+			None => return,
+		};
+		remove_position(&mut self.by_kitty, &entry.kitty_id, position);
+		for account in entry.activity.accounts() {
+			remove_position(&mut self.by_account, account, position);
+		}
+		if let Some(positions) = self.by_block.get_mut(&entry.block_hash) {
+			positions.retain(|p| *p != position);
+			if positions.is_empty() {
+				self.by_block.remove(&entry.block_hash);
+			}
+		}
+	}
+}
+
+fn remove_position<K: std::hash::Hash + Eq>(
+	index: &mut HashMap<K, BTreeSet<u64>>,
+	key: &K,
+	position: u64,
+) {
+	if let Some(positions) = index.get_mut(key) {
+		positions.remove(&position);
+		if positions.is_empty() {
+			index.remove(key);
+		}
+	}
+}
+
+/// Shared in-memory kitty history, written by [`run`] and read by [`KittyRpc`].
+#[derive(Clone, Default)]
+pub struct KittyIndex {
+	entries: Arc<RwLock<Entries>>,
+}
+
+impl KittyIndex {
+	/// History of a single kitty, oldest first.
+	pub fn history(&self, kitty_id: &Hash) -> Vec<KittyHistoryEntry> {
+		let entries = self.entries.read().expect("kitty index lock poisoned; qed");
+		entries.get(entries.by_kitty.get(kitty_id))
+	}
+
+	/// Every indexed event `who` took part in, oldest first.
+	pub fn account_history(&self, who: &AccountId) -> Vec<KittyHistoryEntry> {
+		let entries = self.entries.read().expect("kitty index lock poisoned; qed");
+		entries.get(entries.by_account.get(who))
+	}
+
+	/// Whether events of `block_hash` were indexed. Blocks without kitty events never are.
+	fn contains_block(&self, block_hash: &Hash) -> bool {
+		let entries = self.entries.read().expect("kitty index lock poisoned; qed");
+		entries.by_block.contains_key(block_hash)
+	}
+
+	fn insert(&self, new_entries: Vec<KittyHistoryEntry>) {
+		let mut entries = self.entries.write().expect("kitty index lock poisoned; qed");
+		for entry in new_entries {
+			entries.push(entry);
+		}
+		while entries.entries.len() > MAX_ENTRIES {
+			let oldest = *entries.entries.keys().next().expect("more than MAX_ENTRIES; qed");
+			entries.remove(oldest);
+		}
+	}
+
+	fn retract(&self, block_hash: &Hash) {
+		let mut entries = self.entries.write().expect("kitty index lock poisoned; qed");
+		for position in entries.by_block.remove(block_hash).unwrap_or_default() {
+			entries.remove(position);
+		}
+	}
+}
+
+/// Storage key of `frame_system::Events`.
+fn events_key() -> StorageKey {
+	StorageKey([twox_128(b"System"), twox_128(b"Events")].concat())
+}
+
+/// Decodes the kitty events emitted in `block_hash`. Fails if the state of the block is not
+/// available, for example because it was pruned.
+fn block_entries(
+	client: &FullClient,
+	block_hash: Hash,
+	block_number: BlockNumber,
+) -> Result<Vec<KittyHistoryEntry>, String> {
+	let raw = match client.storage(&BlockId::Hash(block_hash), &events_key()) {
+		Ok(Some(raw)) => raw,
+		Ok(None) => return Ok(Vec::new()),
+		Err(e) => return Err(format!("Failed to read events of {}: {}", block_hash, e)),
+	};
+	let records =
+		match Vec::<frame_system::EventRecord<Event, Hash>>::decode(&mut raw.0.as_slice()) {
+			Ok(records) => records,
+			Err(e) => {
+				log::warn!(target: "kitty-indexer", "Failed to decode events of {}: {}", block_hash, e);
+				return Ok(Vec::new())
+			},
+		};
+
+	Ok(records
+		.into_iter()
+		.enumerate()
+		.filter_map(|(event_index, record)| match record.event {
+			Event::KittyModule(event) => KittyActivity::from_event(event),
+			_ => None,
+		}
+		.map(|(kitty_id, activity)| KittyHistoryEntry {
+			block_number,
+			block_hash,
+			event_index: event_index as u32,
+			kitty_id,
+			activity,
+		}))
+		.collect())
+}
+
+/// Where the persisted index starts and ends.
+#[derive(Default, Encode, Decode)]
+struct StoreMeta {
+	/// Oldest persisted page. Older pages were dropped to stay within [`MAX_ENTRIES`].
+	first_page: u64,
+	/// Page new events are appended to.
+	last_page: u64,
+	/// Highest finalized block indexed so far.
+	indexed_up_to: BlockNumber,
+}
+
+/// Events of finalized blocks, persisted in pages of [`PAGE_SIZE`] in the client's aux storage.
+struct Store {
+	client: Arc<FullClient>,
+	meta: StoreMeta,
+	last_page: Vec<KittyHistoryEntry>,
+}
+
+fn page_key(page: u64) -> Vec<u8> {
+	(b"kitty_index_page", page).encode()
+}
+
+fn read_aux<T: Decode>(client: &FullClient, key: &[u8]) -> Option<T> {
+	match client.get_aux(key) {
+		Ok(Some(raw)) => T::decode(&mut raw.as_slice())
+			.map_err(|e| log::warn!(target: "kitty-indexer", "Failed to decode the index: {}", e))
+			.ok(),
+		Ok(None) => None,
+		Err(e) => {
+			log::warn!(target: "kitty-indexer", "Failed to read the index: {}", e);
+			None
+		},
+	}
+}
+
+impl Store {
+	/// Loads the persisted events into `index`.
+	fn load(client: Arc<FullClient>, index: &KittyIndex) -> Self {
+		let meta: StoreMeta = read_aux(&client, META_KEY).unwrap_or_default();
+		let mut last_page = Vec::new();
+		for page in meta.first_page..=meta.last_page {
+			last_page = read_aux(&client, &page_key(page)).unwrap_or_default();
+			index.insert(last_page.clone());
+		}
+		Self { client, meta, last_page }
+	}
+
+	/// Appends `entries` of the finalized blocks up to `indexed_up_to`, dropping the oldest pages
+	/// past [`MAX_ENTRIES`].
+	fn append(&mut self, indexed_up_to: BlockNumber, entries: Vec<KittyHistoryEntry>) {
+		let mut insert = Vec::new();
+		for entry in entries {
+			self.last_page.push(entry);
+			if self.last_page.len() == PAGE_SIZE {
+				insert.push((page_key(self.meta.last_page), self.last_page.encode()));
+				self.last_page.clear();
+				self.meta.last_page += 1;
+			}
+		}
+		insert.push((page_key(self.meta.last_page), self.last_page.encode()));
+
+		let mut delete = Vec::new();
+		while self.meta.last_page - self.meta.first_page >= (MAX_ENTRIES / PAGE_SIZE) as u64 {
+			delete.push(page_key(self.meta.first_page));
+			self.meta.first_page += 1;
+		}
+		self.meta.indexed_up_to = indexed_up_to;
+		insert.push((META_KEY.to_vec(), self.meta.encode()));
+
+		let insert: Vec<(&[u8], &[u8])> = insert.iter().map(|(k, v)| (&k[..], &v[..])).collect();
+		let delete: Vec<&[u8]> = delete.iter().map(|k| &k[..]).collect();
+		if let Err(e) = self.client.insert_aux(&insert, &delete) {
+			log::warn!(target: "kitty-indexer", "Failed to persist the index: {}", e);
+		}
+	}
+}
+
+/// Indexes and persists the finalized blocks after the last indexed one up to `finalized`.
+fn index_finalized(
+	client: &FullClient,
+	index: &KittyIndex,
+	store: &mut Store,
+	finalized: BlockNumber,
+) {
+	let mut unavailable = 0u32;
+	while store.meta.indexed_up_to < finalized {
+		let from = store.meta.indexed_up_to + 1;
+		let to = finalized.min(store.meta.indexed_up_to.saturating_add(CATCH_UP_BATCH));
+		let mut entries = Vec::new();
+		for number in from..=to {
+			let block_hash = match client.hash(number) {
+				Ok(Some(block_hash)) => block_hash,
+				_ => {
+					unavailable += 1;
+					continue
+				},
+			};
+			let block_entries = match block_entries(client, block_hash, number) {
+				Ok(block_entries) => block_entries,
+				Err(e) => {
+					log::debug!(target: "kitty-indexer", "{}", e);
+					unavailable += 1;
+					continue
+				},
+			};
+			// Blocks that were on the best chain are indexed already.
+			if !index.contains_block(&block_hash) {
+				index.insert(block_entries.clone());
+			}
+			entries.extend(block_entries);
+		}
+		store.append(to, entries);
+	}
+	if unavailable > 0 {
+		log::warn!(
+			target: "kitty-indexer",
+			"Skipped {} finalized blocks whose state is not available. Run an archive node \
+			(`--state-pruning archive`) to index them.",
+			unavailable,
+		);
+	}
+}
+
+/// Indexes a block imported on the best chain, which is not persisted until finalized.
+fn index_best(client: &FullClient, index: &KittyIndex, block_hash: Hash, number: BlockNumber) {
+	if index.contains_block(&block_hash) {
+		return
+	}
+	match block_entries(client, block_hash, number) {
+		Ok(entries) => index.insert(entries),
+		Err(e) => log::warn!(target: "kitty-indexer", "{}", e),
+	}
+}
+
+/// Loads the persisted index, catches up with the finalized chain, then follows the best and
+/// finalized chains and indexes kitty events until both notification streams end.
+pub async fn run(client: Arc<FullClient>, index: KittyIndex) {
+	let imports = client.import_notification_stream().map(Either::Left);
+	let finality = client.finality_notification_stream().map(Either::Right);
+	let mut notifications = futures::stream::select(imports, finality);
+
+	let mut store = Store::load(client.clone(), &index);
+	index_finalized(&client, &index, &mut store, client.info().finalized_number);
+
+	while let Some(notification) = notifications.next().await {
+		match notification {
+			Either::Left(notification) => {
+				if !notification.is_new_best {
+					continue
+				}
+
+				// Blocks imported on a fork that just became best were skipped when they
+				// arrived.
+				if let Some(tree_route) = &notification.tree_route {
+					for retracted in tree_route.retracted() {
+						index.retract(&retracted.hash);
+					}
+					for enacted in tree_route.enacted() {
+						index_best(&client, &index, enacted.hash, enacted.number);
+					}
+				}
+				index_best(&client, &index, notification.hash, notification.header.number);
+			},
+			Either::Right(notification) =>
+				index_finalized(&client, &index, &mut store, notification.header.number),
+		}
+	}
+}
+
+/// Kitty history RPC methods.
+#[rpc(server)]
+pub trait KittyApi {
+	/// Returns every indexed event of `kitty_id`.
+	#[method(name = "kitty_history")]
+	fn history(&self, kitty_id: Hash) -> RpcResult<Vec<KittyHistoryEntry>>;
+
+	/// Returns every indexed kitty event `account` took part in.
+	#[method(name = "kitty_accountHistory")]
+	fn account_history(&self, account: AccountId) -> RpcResult<Vec<KittyHistoryEntry>>;
+}
+
+/// Serves [`KittyApiServer`] from a [`KittyIndex`].
+pub struct KittyRpc {
+	index: KittyIndex,
+}
+
+impl KittyRpc {
+	/// Creates a new [`Self`] reading from `index`.
+	pub fn new(index: KittyIndex) -> Self {
+		Self { index }
+	}
+}
+
+impl KittyApiServer for KittyRpc {
+	fn history(&self, kitty_id: Hash) -> RpcResult<Vec<KittyHistoryEntry>> {
+		Ok(self.index.history(&kitty_id))
+	}
+
+	fn account_history(&self, account: AccountId) -> RpcResult<Vec<KittyHistoryEntry>> {
+		Ok(self.index.account_history(&account))
+	}
+}
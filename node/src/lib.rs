@@ -1,3 +1,5 @@
 pub mod chain_spec;
+pub mod kitty_indexer;
+pub mod poe_rpc;
 pub mod rpc;
 pub mod service;
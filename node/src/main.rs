@@ -7,6 +7,9 @@ mod service;
 mod cli;
 mod command;
 mod command_helper;
+mod kitty_indexer;
+mod poe_command;
+mod poe_rpc;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
@@ -0,0 +1,292 @@
+//! Offline helpers for `pallet_poe`.
+
+use std::path::PathBuf;
+
+use codec::{Decode, Encode};
+use finality_grandpa::voter_set::VoterSet;
+use node_template_runtime::{
+	opaque::{Block, Header},
+	Runtime,
+};
+use pallet_poe::{merkle, DigestProofs, HashAlgorithm, ProofInfoOf, Proofs};
+use sc_finality_grandpa::GrandpaJustification;
+use serde::Serialize;
+use sp_core::{crypto::Ss58Codec, ed25519, hashing::blake2_256, hexdisplay::HexDisplay, H256};
+use sp_finality_grandpa::AuthorityId;
+use sp_runtime::{
+	traits::{BlakeTwo256, Header as HeaderT},
+	BoundedVec,
+};
+use sp_state_machine::{read_proof_check, StorageProof};
+
+use crate::{chain_spec, poe_rpc::ProofCertificate};
+
+/// Parses the command line name of a [`HashAlgorithm`].
+pub(crate) fn parse_algorithm(name: &str) -> Result<HashAlgorithm, String> {
+	match name {
+		"blake2-256" => Ok(HashAlgorithm::Blake2_256),
+		"sha2-256" => Ok(HashAlgorithm::Sha2_256),
+		"keccak-256" => Ok(HashAlgorithm::Keccak256),
+		other => Err(format!(
+			"unknown hash algorithm `{}`, expected blake2-256, sha2-256 or keccak-256",
+			other
+		)),
+	}
+}
+
+/// Parses the SS58 address of a GRANDPA authority.
+fn parse_authority(address: &str) -> Result<AuthorityId, String> {
+	ed25519::Public::from_ss58check(address)
+		.map(Into::into)
+		.map_err(|e| format!("invalid authority `{}`: {:?}", address, e))
+}
+
+/// The `hash-claim` command.
+///
+/// Hashes a file locally so that only its digest ever reaches the chain, and prints the
+/// SCALE encoded `create_digest_claim` call ready to be signed and submitted.
+#[derive(Debug, clap::Parser)]
+pub struct HashClaimCmd {
+	/// File to claim.
+	#[clap(parse(from_os_str))]
+	pub path: PathBuf,
+
+	/// Hash algorithm: blake2-256, sha2-256 or keccak-256.
+	#[clap(long, default_value = "blake2-256", parse(try_from_str = parse_algorithm))]
+	pub algorithm: HashAlgorithm,
+}
+
+impl HashClaimCmd {
+	/// Runs the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let data = std::fs::read(&self.path)?;
+		let digest = self.algorithm.hash(&data);
+
+		let call = node_template_runtime::Call::PoeModule(pallet_poe::Call::create_digest_claim {
+			algorithm: self.algorithm,
+			digest: digest.clone(),
+		});
+
+		println!("digest: 0x{}", HexDisplay::from(&digest));
+		println!("call data: 0x{}", HexDisplay::from(&call.encode()));
+		Ok(())
+	}
+}
+
+/// The `merkle-batch` command.
+///
+/// Builds the Merkle tree of every file in a directory, in file name order, and prints as
+/// JSON the root, the `anchor_batch` call data and the inclusion proof of each file. The
+/// leaf of a file is the blake2-256 hash of its contents.
+#[derive(Debug, clap::Parser)]
+pub struct MerkleBatchCmd {
+	/// Directory holding the documents of the batch.
+	#[clap(parse(from_os_str))]
+	pub dir: PathBuf,
+
+	/// Metadata stored with the anchored batch.
+	#[clap(long, default_value = "")]
+	pub metadata: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchLeaf {
+	file: String,
+	leaf: H256,
+	proof: Vec<H256>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Batch {
+	root: H256,
+	leaf_count: u32,
+	call_data: String,
+	leaves: Vec<BatchLeaf>,
+}
+
+impl MerkleBatchCmd {
+	/// Runs the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let mut files = Vec::new();
+		for entry in std::fs::read_dir(&self.dir)? {
+			let path = entry?.path();
+			if path.is_file() {
+				files.push(path);
+			}
+		}
+		files.sort();
+
+		let mut leaves = Vec::with_capacity(files.len());
+		for path in &files {
+			leaves.push(H256(blake2_256(&std::fs::read(path)?)));
+		}
+		let root = merkle::root(&leaves)
+			.ok_or_else(|| format!("no files found in {}", self.dir.display()))?;
+
+		let call = node_template_runtime::Call::PoeModule(pallet_poe::Call::anchor_batch {
+			root,
+			leaf_count: leaves.len() as u32,
+			metadata: self.metadata.as_bytes().to_vec(),
+		});
+
+		let batch = Batch {
+			root,
+			leaf_count: leaves.len() as u32,
+			call_data: format!("0x{}", HexDisplay::from(&call.encode())),
+			leaves: files
+				.iter()
+				.zip(&leaves)
+				.enumerate()
+				.map(|(index, (path, leaf))| BatchLeaf {
+					file: path.display().to_string(),
+					leaf: *leaf,
+					proof: merkle::proof(&leaves, index).expect("index is in bounds; qed"),
+				})
+				.collect(),
+		};
+
+		let json = serde_json::to_string_pretty(&batch)
+			.map_err(|e| format!("failed to serialize batch: {}", e))?;
+		println!("{}", json);
+		Ok(())
+	}
+}
+
+/// The `build-spec-with-claims` command.
+///
+/// Prints the chain spec of the development or local chain with the claims listed in a JSON
+/// file made at genesis, in the format read by [`chain_spec::load_genesis_claims`].
+#[derive(Debug, clap::Parser)]
+pub struct BuildSpecWithClaimsCmd {
+	/// JSON file listing the genesis claims.
+	#[clap(parse(from_os_str))]
+	pub claims: PathBuf,
+
+	/// Chain to start from: dev or local.
+	#[clap(long, default_value = "dev")]
+	pub chain: String,
+
+	/// Print the genesis storage instead of the genesis config.
+	#[clap(long)]
+	pub raw: bool,
+}
+
+impl BuildSpecWithClaimsCmd {
+	/// Runs the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let claims = chain_spec::load_genesis_claims(&self.claims)?;
+		let spec = match self.chain.as_str() {
+			"dev" => chain_spec::development_config_with_claims(claims)?,
+			"local" => chain_spec::local_testnet_config_with_claims(claims)?,
+			other => return Err(format!("unknown chain `{}`, expected dev or local", other).into()),
+		};
+		println!("{}", sc_service::ChainSpec::as_json(&spec, self.raw)?);
+		Ok(())
+	}
+}
+
+/// The `verify-proof` command.
+///
+/// Checks a certificate exported with the `poe_proofCertificate` RPC without connecting to
+/// any node. The GRANDPA justification must finalize the certificate's header under the given
+/// authority set, the storage proof must show the claim under that header's state root, and
+/// the storage key must be the one of the claim.
+#[derive(Debug, clap::Parser)]
+pub struct VerifyProofCmd {
+	/// JSON file holding the certificate.
+	#[clap(parse(from_os_str))]
+	pub certificate: PathBuf,
+
+	/// SS58 address of a GRANDPA authority trusted to finalize the header. Repeat for every
+	/// authority of the set.
+	#[clap(long = "authority", required = true, parse(try_from_str = parse_authority))]
+	pub authorities: Vec<AuthorityId>,
+
+	/// Id of the authority set.
+	#[clap(long, default_value = "0")]
+	pub set_id: u64,
+}
+
+impl VerifyProofCmd {
+	/// Runs the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let certificate: ProofCertificate =
+			serde_json::from_slice(&std::fs::read(&self.certificate)?)
+				.map_err(|e| format!("invalid certificate: {}", e))?;
+
+		let header = Header::decode(&mut &certificate.header[..])
+			.map_err(|e| format!("invalid header: {}", e))?;
+		let hash = header.hash();
+		if hash != certificate.at {
+			return Err(format!("header hashes to {:?}, not {:?}", hash, certificate.at).into())
+		}
+
+		let justification = certificate
+			.justification
+			.as_ref()
+			.ok_or_else(|| "the certificate holds no GRANDPA justification".to_string())?;
+		let voters = VoterSet::new(self.authorities.iter().cloned().map(|id| (id, 1)))
+			.ok_or_else(|| "invalid authority set".to_string())?;
+		GrandpaJustification::<Block>::decode_and_verify_finalizes(
+			justification,
+			(hash, *header.number()),
+			self.set_id,
+			&voters,
+		)
+		.map_err(|e| format!("invalid justification: {}", e))?;
+
+		if !claim_keys(&certificate.claim).contains(&certificate.key.0) {
+			return Err("the storage key is not the one of the claim".to_string().into())
+		}
+		let proof = StorageProof::new(certificate.proof.iter().map(|node| node.to_vec()));
+		let value = read_proof_check::<BlakeTwo256, _>(
+			*header.state_root(),
+			proof,
+			[&certificate.key[..]],
+		)
+		.map_err(|e| format!("invalid storage proof: {}", e))?
+		.remove(&certificate.key.0)
+		.flatten()
+		.ok_or_else(|| "the storage proof shows no claim".to_string())?;
+		if value != certificate.value.0 {
+			return Err("the storage proof shows a different claim".to_string().into())
+		}
+
+		let proof_info = ProofInfoOf::<Runtime>::decode(&mut &value[..])
+			.map_err(|e| format!("invalid claim value: {}", e))?;
+		if proof_info.block_number != certificate.block_number ||
+			proof_info.moment != certificate.timestamp ||
+			proof_info.extrinsic_index != certificate.extrinsic_index
+		{
+			return Err("the certificate does not match the proven claim".to_string().into())
+		}
+
+		println!("claim: 0x{}", HexDisplay::from(&certificate.claim.0));
+		println!("owner: {}", proof_info.owner);
+		println!("block: #{}", proof_info.block_number);
+		println!("timestamp: {}", proof_info.moment);
+		if let Some(index) = proof_info.extrinsic_index {
+			println!("extrinsic: {}-{}", proof_info.block_number, index);
+		}
+		println!("finalized at: #{} ({:?})", header.number(), hash);
+		Ok(())
+	}
+}
+
+/// Every storage key `claim` can be recorded under, as raw bytes or as a digest.
+fn claim_keys(claim: &[u8]) -> Vec<Vec<u8>> {
+	let mut keys = Vec::new();
+	if let Ok(bounded_claim) =
+		<BoundedVec<u8, <Runtime as pallet_poe::Config>::MaxclaimLength>>::try_from(claim.to_vec())
+	{
+		keys.push(Proofs::<Runtime>::hashed_key_for(&bounded_claim));
+	}
+	for algorithm in [HashAlgorithm::Blake2_256, HashAlgorithm::Sha2_256, HashAlgorithm::Keccak256] {
+		if let Ok(claim_digest) = pallet_poe::Pallet::<Runtime>::claim_digest(algorithm, claim) {
+			keys.push(DigestProofs::<Runtime>::hashed_key_for(&claim_digest));
+		}
+	}
+	keys
+}
@@ -0,0 +1,371 @@
+//! RPC methods of `pallet_poe`, served through the `poe_` namespace.
+
+use std::sync::Arc;
+
+use codec::{Decode, Encode};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use node_template_runtime::{opaque::Block, AccountId, BlockNumber, Hash, Moment};
+use pallet_poe::HashAlgorithm;
+use pallet_poe_runtime_api::{ClaimRecord, PoeApi as PoeRuntimeApi, RevocationInfo};
+use sc_client_api::{Backend, BlockBackend, ProofProvider, StorageProvider};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	hashing::twox_128,
+	offchain::{OffchainStorage, STORAGE_PREFIX},
+	storage::StorageKey,
+	Bytes, H256,
+};
+use sp_finality_grandpa::GRANDPA_ENGINE_ID;
+use sp_runtime::generic::BlockId;
+
+/// Error code of a failed runtime API call.
+const RUNTIME_ERROR: i32 = 1;
+/// Error code of a call needing offchain indexing on a node without it.
+const OFFCHAIN_INDEXING_DISABLED: i32 = 2;
+/// Error code of a failed read from the node's database.
+const CLIENT_ERROR: i32 = 3;
+
+fn runtime_error(e: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", e)),
+	))
+	.into()
+}
+
+fn client_error(e: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(CLIENT_ERROR, "Client error", Some(format!("{:?}", e))))
+		.into()
+}
+
+/// A single-owner claim with the time it was recorded.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimInfo {
+	/// The raw claim bytes, or the digest of a digest claim.
+	pub claim: Bytes,
+	/// The algorithm of a digest claim, `None` for a raw claim.
+	pub algorithm: Option<String>,
+	/// Current owner of the claim, the account of `org` for a claim of an organization.
+	pub owner: AccountId,
+	/// Block the claim was created or last transferred in.
+	pub block_number: BlockNumber,
+	/// Timestamp of `block_number` in milliseconds, as recorded with the claim or, for older
+	/// claims, if the node still has the state of that block.
+	pub block_timestamp: Option<u64>,
+	/// Block from which the claim no longer holds.
+	pub expires_at: Option<BlockNumber>,
+	/// The organization holding the claim, if any.
+	pub org: Option<u32>,
+	/// The member that submitted a claim of an organization.
+	pub submitter: Option<AccountId>,
+}
+
+/// The tombstone of a revoked claim.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Revocation {
+	/// Owner of the claim when it was revoked.
+	pub owner: AccountId,
+	/// Account that signed the revocation.
+	pub revoked_by: AccountId,
+	/// Block the claim was revoked in.
+	pub block_number: BlockNumber,
+	/// Timestamp of `block_number` in milliseconds, if the node still has its state.
+	pub block_timestamp: Option<u64>,
+	/// Reason given for the revocation.
+	pub reason: Bytes,
+}
+
+/// Everything needed to check a claim offline against a finalized header.
+///
+/// `proof` holds the trie nodes leading from the state root in `header` to `value` at `key`,
+/// as returned by `state_getReadProof`. Checked by the `verify-proof` command.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofCertificate {
+	/// The claim as it was looked up.
+	pub claim: Bytes,
+	/// Block the claim was created or last transferred in.
+	pub block_number: BlockNumber,
+	/// Index of the extrinsic within `block_number` that set the claim, if known.
+	pub extrinsic_index: Option<u32>,
+	/// Timestamp of `block_number` in milliseconds, zero if it was not recorded.
+	pub timestamp: Moment,
+	/// Block whose state `proof` is against.
+	pub at: Hash,
+	/// SCALE encoded header of `at`.
+	pub header: Bytes,
+	/// SCALE encoded GRANDPA justification finalizing `at`. Nodes only keep justifications of
+	/// some blocks, such as the last of each authority set.
+	pub justification: Option<Bytes>,
+	/// Storage key of the claim.
+	pub key: Bytes,
+	/// SCALE encoded `ProofInfo` stored at `key`.
+	pub value: Bytes,
+	/// Trie nodes proving `value` at `key`.
+	pub proof: Vec<Bytes>,
+}
+
+/// Proof of existence RPC methods.
+#[rpc(server)]
+pub trait PoeApi {
+	/// Whether `proof` shows that `leaf` belongs to a batch anchored at `root`, as of block
+	/// `at` or the best block.
+	#[method(name = "poe_verifyInclusion")]
+	fn verify_inclusion(
+		&self,
+		leaf: H256,
+		proof: Vec<H256>,
+		root: H256,
+		at: Option<Hash>,
+	) -> RpcResult<bool>;
+
+	/// Looks up a claim by its raw bytes or by its digest.
+	#[method(name = "poe_getClaim")]
+	fn get_claim(&self, claim: Bytes, at: Option<Hash>) -> RpcResult<Option<ClaimInfo>>;
+
+	/// Every claim owned by `account`.
+	#[method(name = "poe_claimsByOwner")]
+	fn claims_by_owner(&self, account: AccountId, at: Option<Hash>) -> RpcResult<Vec<ClaimInfo>>;
+
+	/// Looks up the tombstone of a revoked claim by its raw bytes or by its digest.
+	#[method(name = "poe_getRevocation")]
+	fn get_revocation(&self, claim: Bytes, at: Option<Hash>) -> RpcResult<Option<Revocation>>;
+
+	/// A certificate that a claim exists, proven against block `at` or, by default, the last
+	/// finalized block this node holds a GRANDPA justification of. Only certificates with a
+	/// justification are checkable offline, so claims made after that block have none yet.
+	#[method(name = "poe_proofCertificate")]
+	fn proof_certificate(
+		&self,
+		claim: Bytes,
+		at: Option<Hash>,
+	) -> RpcResult<Option<ProofCertificate>>;
+
+	/// The document behind a claim made with `create_indexed_claim`, by its digest, from
+	/// the offchain database of this node.
+	#[method(name = "poe_getPayload")]
+	fn get_payload(&self, digest: Bytes) -> RpcResult<Option<Bytes>>;
+}
+
+/// Serves [`PoeApiServer`] by calling into the runtime.
+pub struct Poe<C, B: Backend<Block>> {
+	client: Arc<C>,
+	offchain_storage: Option<B::OffchainStorage>,
+}
+
+impl<C, B: Backend<Block>> Poe<C, B> {
+	/// Creates a new [`Self`] querying `client`, and `offchain_storage` for claim payloads
+	/// if the node indexes them.
+	pub fn new(client: Arc<C>, offchain_storage: Option<B::OffchainStorage>) -> Self {
+		Self { client, offchain_storage }
+	}
+}
+
+impl<C, B> Poe<C, B>
+where
+	C: HeaderBackend<Block> + StorageProvider<Block, B>,
+	B: Backend<Block>,
+{
+	fn at(&self, at: Option<Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+
+	/// Reads `pallet_timestamp::Now` as of `number`.
+	fn block_timestamp(&self, number: BlockNumber) -> Option<u64> {
+		let hash = self.client.hash(number).ok()??;
+		let key = StorageKey([twox_128(b"Timestamp"), twox_128(b"Now")].concat());
+		let raw = self.client.storage(&BlockId::hash(hash), &key).ok()??;
+		u64::decode(&mut raw.0.as_slice()).ok()
+	}
+
+	fn claim_info(&self, record: ClaimRecord<AccountId, BlockNumber, Moment>) -> ClaimInfo {
+		// Claims made before timestamps were recorded have a zero moment.
+		let block_timestamp = if record.moment > 0 {
+			Some(record.moment)
+		} else {
+			self.block_timestamp(record.block_number)
+		};
+		ClaimInfo {
+			claim: record.claim.into(),
+			algorithm: record.algorithm.map(|algorithm| format!("{:?}", algorithm)),
+			owner: record.owner,
+			block_number: record.block_number,
+			block_timestamp,
+			expires_at: record.expires_at,
+			org: record.org,
+			submitter: record.submitter,
+		}
+	}
+
+	fn revocation(&self, info: RevocationInfo<AccountId, BlockNumber, Vec<u8>>) -> Revocation {
+		Revocation {
+			owner: info.owner,
+			revoked_by: info.revoked_by,
+			block_number: info.block_number,
+			block_timestamp: self.block_timestamp(info.block_number),
+			reason: info.reason.into(),
+		}
+	}
+}
+
+impl<C, B> Poe<C, B>
+where
+	C: HeaderBackend<Block> + BlockBackend<Block>,
+	B: Backend<Block>,
+{
+	/// The last finalized block this node holds a GRANDPA justification of, walking back from
+	/// the finalized head.
+	fn last_justified(&self) -> RpcResult<Hash> {
+		let mut hash = self.client.info().finalized_hash;
+		loop {
+			let id = BlockId::hash(hash);
+			let justified = self
+				.client
+				.justifications(&id)
+				.map_err(client_error)?
+				.map_or(false, |justifications| justifications.get(GRANDPA_ENGINE_ID).is_some());
+			if justified {
+				return Ok(hash)
+			}
+			let header = self
+				.client
+				.header(id)
+				.map_err(client_error)?
+				.ok_or_else(|| client_error("unknown block"))?;
+			if header.number == 0 {
+				return Err(client_error("no justified block"))
+			}
+			hash = header.parent_hash;
+		}
+	}
+}
+
+impl<C, B> PoeApiServer for Poe<C, B>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + StorageProvider<Block, B>,
+	C: BlockBackend<Block> + ProofProvider<Block> + Send + Sync + 'static,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber, Moment>,
+	B: Backend<Block> + Send + Sync + 'static,
+{
+	fn verify_inclusion(
+		&self,
+		leaf: H256,
+		proof: Vec<H256>,
+		root: H256,
+		at: Option<Hash>,
+	) -> RpcResult<bool> {
+		self.client
+			.runtime_api()
+			.verify_inclusion(&self.at(at), leaf, proof, root)
+			.map_err(runtime_error)
+	}
+
+	fn get_claim(&self, claim: Bytes, at: Option<Hash>) -> RpcResult<Option<ClaimInfo>> {
+		let record =
+			self.client.runtime_api().claim(&self.at(at), claim.to_vec()).map_err(runtime_error)?;
+		Ok(record.map(|record| self.claim_info(record)))
+	}
+
+	fn claims_by_owner(&self, account: AccountId, at: Option<Hash>) -> RpcResult<Vec<ClaimInfo>> {
+		let records = self
+			.client
+			.runtime_api()
+			.claims_by_owner(&self.at(at), account)
+			.map_err(runtime_error)?;
+		Ok(records.into_iter().map(|record| self.claim_info(record)).collect())
+	}
+
+	fn get_revocation(&self, claim: Bytes, at: Option<Hash>) -> RpcResult<Option<Revocation>> {
+		let info = self
+			.client
+			.runtime_api()
+			.revocation(&self.at(at), claim.to_vec())
+			.map_err(runtime_error)?;
+		Ok(info.map(|info| self.revocation(info)))
+	}
+
+	fn proof_certificate(
+		&self,
+		claim: Bytes,
+		at: Option<Hash>,
+	) -> RpcResult<Option<ProofCertificate>> {
+		let at = match at {
+			Some(at) => at,
+			None => self.last_justified()?,
+		};
+		let id = BlockId::hash(at);
+		let anchor = match self
+			.client
+			.runtime_api()
+			.proof_anchor(&id, claim.to_vec())
+			.map_err(runtime_error)?
+		{
+			Some(anchor) => anchor,
+			None => return Ok(None),
+		};
+
+		let header = self
+			.client
+			.header(id)
+			.map_err(client_error)?
+			.ok_or_else(|| client_error("unknown block"))?;
+		let key = StorageKey(anchor.key);
+		let value = self
+			.client
+			.storage(&id, &key)
+			.map_err(client_error)?
+			.ok_or_else(|| client_error("claim missing from state"))?;
+		let proof = self
+			.client
+			.read_proof(&id, &mut std::iter::once(key.0.as_slice()))
+			.map_err(client_error)?;
+		let justification = self
+			.client
+			.justifications(&id)
+			.map_err(client_error)?
+			.and_then(|justifications| justifications.into_justification(GRANDPA_ENGINE_ID));
+
+		Ok(Some(ProofCertificate {
+			claim,
+			block_number: anchor.block_number,
+			extrinsic_index: anchor.extrinsic_index,
+			timestamp: anchor.moment,
+			at,
+			header: header.encode().into(),
+			justification: justification.map(Into::into),
+			key: key.0.into(),
+			value: value.0.into(),
+			proof: proof.into_iter_nodes().map(Into::into).collect(),
+		}))
+	}
+
+	fn get_payload(&self, digest: Bytes) -> RpcResult<Option<Bytes>> {
+		let storage = self.offchain_storage.as_ref().ok_or_else(|| {
+			JsonRpseeError::from(CallError::Custom(ErrorObject::owned(
+				OFFCHAIN_INDEXING_DISABLED,
+				"Offchain indexing is disabled",
+				Some("start the node with `--enable-offchain-indexing true`"),
+			)))
+		})?;
+		// The offchain database is not fork aware, so only return payloads that still
+		// match their digest.
+		let payload = [HashAlgorithm::Blake2_256, HashAlgorithm::Sha2_256, HashAlgorithm::Keccak256]
+			.into_iter()
+			.filter(|algorithm| algorithm.digest_len() as usize == digest.len())
+			.find_map(|algorithm| {
+				let payload =
+					storage.get(STORAGE_PREFIX, &pallet_poe::payload::key(algorithm, &digest))?;
+				(algorithm.hash(&payload) == digest.0).then(|| payload)
+			});
+		Ok(payload.map(Into::into))
+	}
+}
@@ -221,14 +221,38 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	// Index kitty events of imported blocks for the `kitty_` RPC namespace.
+	let kitty_index = crate::kitty_indexer::KittyIndex::default();
+	task_manager.spawn_handle().spawn(
+		"kitty-indexer",
+		None,
+		crate::kitty_indexer::run(client.clone(), kitty_index.clone()),
+	);
+
+	// `pallet_poe` writes claim payloads through offchain indexing, which is off by default.
+	let offchain_indexing = config.offchain_worker.indexing_enabled;
+	if !offchain_indexing {
+		log::warn!(
+			"Offchain indexing is disabled, `poe_getPayload` will not serve claim payloads. \
+			Start the node with `--enable-offchain-indexing true` to keep them."
+		);
+	}
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
+		let backend = backend.clone();
 		let pool = transaction_pool.clone();
 
 		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
-			crate::rpc::create_full(deps).map_err(Into::into)
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				backend: backend.clone(),
+				offchain_indexing,
+				pool: pool.clone(),
+				deny_unsafe,
+				kitty_index: kitty_index.clone(),
+			};
+			crate::rpc::create_full::<_, _, FullBackend>(deps).map_err(Into::into)
 		})
 	};
 
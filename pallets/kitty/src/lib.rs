@@ -8,31 +8,77 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
+
+// kitty 状态变化的回调，供下游 pallet 订阅
+pub trait OnKittyChange<AccountId, KittyId> {
+    // 生成新的 kitty（创建或繁殖）
+    fn on_mint(_owner: &AccountId, _kitty_id: &KittyId) {}
+    // kitty 所有权转移
+    fn on_transfer(_from: &AccountId, _to: &AccountId, _kitty_id: &KittyId) {}
+    // 通过两只 kitty 繁殖出新的 kitty
+    fn on_breed(_owner: &AccountId, _kitty_id: &KittyId, _parents: (&KittyId, &KittyId)) {}
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, KittyId> OnKittyChange<AccountId, KittyId> for Tuple {
+    fn on_mint(owner: &AccountId, kitty_id: &KittyId) {
+        for_tuples!( #( Tuple::on_mint(owner, kitty_id); )* );
+    }
+
+    fn on_transfer(from: &AccountId, to: &AccountId, kitty_id: &KittyId) {
+        for_tuples!( #( Tuple::on_transfer(from, to, kitty_id); )* );
+    }
+
+    fn on_breed(owner: &AccountId, kitty_id: &KittyId, parents: (&KittyId, &KittyId)) {
+        for_tuples!( #( Tuple::on_breed(owner, kitty_id, parents); )* );
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::{transactional, pallet_prelude::{*, ValueQuery, DispatchResultWithPostInfo}, traits::{Currency, Randomness, ExistenceRequirement, ReservableCurrency}, Twox64Concat, BoundedVec, sp_runtime::traits::Hash};
+    use frame_support::{transactional, pallet_prelude::{*, ValueQuery, DispatchResultWithPostInfo}, traits::{Currency, Randomness, ExistenceRequirement, NamedReservableCurrency, BalanceStatus, tokens::fungibles}, Twox64Concat, BoundedVec, PalletId, sp_runtime::{traits::{Hash, AccountIdConversion, AtLeast32BitUnsigned, One, Zero, Saturating, SaturatedConversion}, Perquintill}};
 	use frame_system::pallet_prelude::*;
     use scale_info::TypeInfo;
+    use frame_support::traits::tokens::fungibles::{Create as _, Inspect as _, Mutate as _};
     use sp_io::hashing::blake2_128;
+    use super::OnKittyChange;
 
     // 定义账户类型
     type AccountOf<T> = <T as frame_system::Config>::AccountId;
     // 定义资产类型
-    type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountOf<T>>>::Balance;
+    pub(crate) type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountOf<T>>>::Balance;
     // 定义质押资产类型
-    type ReserveBalanceOf<T> = <<T as Config>::ReservableCurrency as Currency<AccountOf<T>>>::Balance;
+    pub(crate) type ReserveBalanceOf<T> = <<T as Config>::ReservableCurrency as Currency<AccountOf<T>>>::Balance;
+    // 定义质押标识类型
+    type ReserveIdOf<T> = <<T as Config>::ReservableCurrency as NamedReservableCurrency<AccountOf<T>>>::ReserveIdentifier;
+    // 定义份额资产类型
+    type ShareBalanceOf<T> = <<T as Config>::ShareAssets as fungibles::Inspect<AccountOf<T>>>::Balance;
 
     #[derive(Clone, Encode, Decode, PartialEq, MaxEncodedLen, TypeInfo)]
     #[scale_info(skip_type_params(T))]
-    // 定义kitty结构体 包含 dna、价格、性别、所有者 元素
+    // 定义kitty结构体 包含 dna、价格、性别、所有者、质押 元素
     pub struct Kitty<T: Config> {
         pub dna: [u8; 16],
         pub price: Option<BalanceOf<T>>,
         pub gender: Gender,
         pub owner: AccountOf<T>,
+        // 随 kitty 一起转移的质押
+        pub deposit: ReserveBalanceOf<T>,
     }
 
     #[derive(Clone, Encode, Decode, PartialEq, MaxEncodedLen, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    // 定义碎片化 kitty 结构体 包含 份额资产id、份额总量、质押人、收购价、是否已被收购 元素
+    pub struct Fraction<T: Config> {
+        pub asset_id: T::ShareAssetId,
+        pub shares: ShareBalanceOf<T>,
+        pub curator: AccountOf<T>,
+        pub buyout_price: Option<BalanceOf<T>>,
+        pub bought_out: bool,
+    }
+
+    #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
     // 定义性别枚举体
     pub enum Gender {
         Male,
@@ -50,15 +96,37 @@ pub mod pallet {
         #[pallet::constant]
         // 持有kitty的最大数量限制
         type MaxKittyOwned: Get<u32>;
-        // 质押资产
-		type ReservableCurrency: ReservableCurrency<Self::AccountId>;
+        // 质押资产，按 ReserveId 命名质押
+		type ReservableCurrency: NamedReservableCurrency<Self::AccountId>;
 		#[pallet::constant]
-        // 质押费用
+        // 质押标识
+		type ReserveId: Get<ReserveIdOf<Self>>;
+		#[pallet::constant]
+        // 第一只 kitty 的质押费用
 		type ReservationFee: Get<ReserveBalanceOf<Self>>;
+		#[pallet::constant]
+        // 账户每多持有一只 kitty，新 kitty 增加的质押费用
+		type ReservationFeeStep: Get<ReserveBalanceOf<Self>>;
+        // kitty 生成、交易、繁殖时的回调
+        type OnKittyChange: OnKittyChange<Self::AccountId, Self::Hash>;
+        #[pallet::constant]
+        // 碎片化 kitty 金库账户的 PalletId
+        type PalletId: Get<PalletId>;
+        // 份额资产 id
+        type ShareAssetId: Member + Parameter + AtLeast32BitUnsigned + Default + Copy + MaxEncodedLen;
+        // 份额资产，负责创建、铸造、销毁份额
+        type ShareAssets: fungibles::Create<Self::AccountId, AssetId = Self::ShareAssetId>
+            + fungibles::Mutate<Self::AccountId, AssetId = Self::ShareAssetId>;
+        // 管理员权限，用于强制转移、暂停市场和繁殖、拉黑 kitty
+        type ForceOrigin: EnsureOrigin<Self::Origin>;
     }
 
+    // 存储版本
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
     #[pallet::storage]
@@ -73,17 +141,71 @@ pub mod pallet {
     // 所有者账户 对应 kitty id集合。集合有MaxKittyOwned限制长度
     pub type KittiesOwned<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BoundedVec<T::Hash, T::MaxKittyOwned>, ValueQuery>;
 
+    #[pallet::storage]
+    // kitty id 对应 碎片化信息
+    pub type Fractions<T: Config> = StorageMap<_, Twox64Concat, T::Hash, Fraction<T>>;
+
+    #[pallet::storage]
+    // 下一个份额资产 id
+    pub type NextShareAssetId<T: Config> = StorageValue<_, T::ShareAssetId, ValueQuery>;
+
+    #[pallet::storage]
+    // 市场（设置价格、购买）是否暂停
+    pub type MarketplacePaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    #[pallet::storage]
+    // 繁殖是否暂停
+    pub type BreedingPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    #[pallet::storage]
+    // 被拉黑的 kitty
+    pub type Blacklist<T: Config> = StorageMap<_, Twox64Concat, T::Hash, (), OptionQuery>;
+
+    #[pallet::storage]
+    // 账户单独设置的最大持有数量，不能超过 MaxKittyOwned
+    pub type MaxOwnedOverride<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, OptionQuery>;
+
+    #[pallet::storage]
+    // 当前 kitty 质押所按的费用曲线 (ReservationFee, ReservationFeeStep)，
+    // 与配置不同时由 ReReserveDeposits 迁移重新质押
+    pub type AppliedReservationFees<T: Config> = StorageValue<_, (ReserveBalanceOf<T>, ReserveBalanceOf<T>), OptionQuery>;
+
+    #[pallet::storage]
+    // ReReserveDeposits 的进度: 不存在表示没有进行中的重新质押，
+    // 存在时为最后一个处理完的账户，None 表示还没有处理任何账户
+    pub type ReReserveProgress<T: Config> = StorageValue<_, Option<T::AccountId>, OptionQuery>;
+
     #[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
         // 成功创建kitty
-        Created(T::AccountId, T::Hash),
+        Created { owner: T::AccountId, kitty_id: T::Hash, dna: [u8; 16], gender: Gender },
+        // 成功繁殖kitty
+        Bred { owner: T::AccountId, kitty_id: T::Hash, dna: [u8; 16], gender: Gender, parents: (T::Hash, T::Hash) },
         // 为kitty设置价格
-        PriceSet(T::AccountId, T::Hash, Option<BalanceOf<T>>),
+        PriceSet { owner: T::AccountId, kitty_id: T::Hash, price: Option<BalanceOf<T>> },
         // 交易成功
-        Transferred(T::AccountId, T::AccountId, T::Hash),
+        Transferred { from: T::AccountId, to: T::AccountId, kitty_id: T::Hash },
         // 成功购买
-        Bought(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
+        Bought { buyer: T::AccountId, seller: T::AccountId, kitty_id: T::Hash, price: BalanceOf<T> },
+        // kitty 被碎片化
+        Fractionalized { owner: T::AccountId, kitty_id: T::Hash, asset_id: T::ShareAssetId, shares: ShareBalanceOf<T> },
+        // 持有全部份额的账户赎回 kitty
+        Redeemed { owner: T::AccountId, kitty_id: T::Hash },
+        // kitty 被整体收购
+        BoughtOut { buyer: T::AccountId, kitty_id: T::Hash, price: BalanceOf<T> },
+        // 份额持有人领取收购款
+        ProceedsClaimed { who: T::AccountId, kitty_id: T::Hash, shares: ShareBalanceOf<T>, proceeds: BalanceOf<T> },
+        // 管理员强制转移 kitty
+        ForceTransferred { from: T::AccountId, to: T::AccountId, kitty_id: T::Hash },
+        // 市场暂停状态改变
+        MarketplacePausedSet { paused: bool },
+        // 繁殖暂停状态改变
+        BreedingPausedSet { paused: bool },
+        // kitty 拉黑状态改变
+        BlacklistSet { kitty_id: T::Hash, blacklisted: bool },
+        // 账户最大持有数量改变
+        MaxOwnedOverrideSet { account: T::AccountId, max_owned: Option<u32> },
     }
 
     #[pallet::error]
@@ -110,10 +232,43 @@ pub mod pallet {
         NotEnoughBalance,
         // 质押资产失败
         ReserveBalanceFailed,
+        // 份额数量不能为 0
+        ZeroShares,
+        // 份额资产 id 溢出
+        ShareAssetIdOverflow,
+        // kitty 没有被碎片化
+        NotFractionalized,
+        // 赎回需要持有全部份额
+        InsufficientShares,
+        // kitty 没有设置收购价
+        BuyoutNotAllowed,
+        // kitty 已经被收购
+        AlreadyBoughtOut,
+        // kitty 还没有被收购
+        NotBoughtOut,
+        // 账户没有份额
+        NoShares,
+        // 市场已暂停
+        MarketplacePaused,
+        // 繁殖已暂停
+        BreedingPaused,
+        // kitty 已被拉黑
+        KittyBlacklisted,
+        // 单独设置的最大持有数量不能超过 MaxKittyOwned
+        MaxOwnedOverrideTooHigh,
+        // kitty 已被碎片化，锁定在金库中
+        KittyFractionalized,
+        // kitty 上一轮碎片化的收购款还没有领取完，不能再次碎片化
+        AlreadyFractionalized,
     }
 
     #[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        // 分批完成费用曲线调整后的重新质押
+        fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            crate::migrations::re_reserve_step::<T>(remaining_weight)
+        }
+    }
 
     #[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -122,8 +277,8 @@ pub mod pallet {
         pub fn create_kitty(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
             // 调用生成kitty 方法
-            let kitty_id = Self::mint(&sender, None, None)?;
-            Self::deposit_event(Event::Created(sender, kitty_id));
+            let (kitty_id, kitty) = Self::mint(&sender, None, None)?;
+            Self::deposit_event(Event::Created { owner: sender, kitty_id, dna: kitty.dna, gender: kitty.gender });
             Ok(().into())
         }
 
@@ -131,15 +286,18 @@ pub mod pallet {
         // 设置 kitty 价格
         pub fn set_price(origin: OriginFor<T>, kitty_id: T::Hash, price: Option<BalanceOf<T>>) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
+            // 确保市场没有暂停
+            ensure!(!<MarketplacePaused<T>>::get(), <Error<T>>::MarketplacePaused);
             // 确保 kitty id 存在，并且归所有者 所有
             ensure!(Self::is_kitty_owner(&sender, &kitty_id)?, <Error<T>>::NotKittyOwner);
+            Self::ensure_not_blacklisted(&kitty_id)?;
             // 确保 kitty 存在
             let mut kitty = <Kitties<T>>::get(&kitty_id).ok_or(<Error<T>>::KittyNotExist)?;
             // 设置价格
             kitty.price = price;
             // 重新插入
             <Kitties<T>>::insert(&kitty_id, kitty);
-            Self::deposit_event(Event::PriceSet(sender, kitty_id, price));
+            Self::deposit_event(Event::PriceSet { owner: sender, kitty_id, price });
             Ok(().into())
         }
 
@@ -149,11 +307,12 @@ pub mod pallet {
             let sender = ensure_signed(origin)?;
             // 确保 kitty id 存在，并且归所有者 所有
             ensure!(Self::is_kitty_owner(&sender, &kitty_id)?, <Error<T>>::NotKittyOwner);
+            Self::ensure_not_blacklisted(&kitty_id)?;
             // 确保 交易双方非同一个账户
             ensure!(sender != to, <Error<T>>::TransferToSelf);
             // 开始交易
             Self::transfer_kitty_to(&kitty_id, &to)?;
-            Self::deposit_event(Event::Transferred(sender, to, kitty_id));
+            Self::deposit_event(Event::Transferred { from: sender, to, kitty_id });
             Ok(().into())
         }
 
@@ -166,6 +325,9 @@ pub mod pallet {
 			bid_price: BalanceOf<T>
 		) -> DispatchResultWithPostInfo {
 			let buyer = ensure_signed(origin)?;
+            // 确保市场没有暂停
+            ensure!(!<MarketplacePaused<T>>::get(), <Error<T>>::MarketplacePaused);
+            Self::ensure_not_blacklisted(&kitty_id)?;
 
             // 确保kitty存在
 			let kitty = <Kitties<T>>::get(&kitty_id).ok_or(<Error<T>>::KittyNotExist)?;
@@ -186,20 +348,208 @@ pub mod pallet {
             let seller = kitty.owner;
             // 交易kitty
             Self::transfer_kitty_to(&kitty_id, &buyer)?;
-            Self::deposit_event(Event::Bought(buyer, seller, kitty_id, bid_price));
+            Self::deposit_event(Event::Bought { buyer, seller, kitty_id, price: bid_price });
 			Ok(().into())
 		}
 
         #[pallet::weight(100)]
         pub fn breed_kitty(origin: OriginFor<T>, parent1: T::Hash, parent2: T::Hash) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
+            // 确保繁殖没有暂停
+            ensure!(!<BreedingPaused<T>>::get(), <Error<T>>::BreedingPaused);
             // 确保我有这两个kitty
             ensure!(Self::is_kitty_owner(&sender, &parent1)?, <Error<T>>::NotKittyOwner);
 			ensure!(Self::is_kitty_owner(&sender, &parent2)?, <Error<T>>::NotKittyOwner);
+            Self::ensure_not_blacklisted(&parent1)?;
+            Self::ensure_not_blacklisted(&parent2)?;
             // 繁殖出 dna
             let new_dna = Self::breed_dna(&parent1, &parent2)?;
             // 制造出 kitty
-			Self::mint(&sender, Some(new_dna), None)?;
+			let (kitty_id, kitty) = Self::mint(&sender, Some(new_dna), None)?;
+            T::OnKittyChange::on_breed(&sender, &kitty_id, (&parent1, &parent2));
+            Self::deposit_event(Event::Bred {
+                owner: sender,
+                kitty_id,
+                dna: kitty.dna,
+                gender: kitty.gender,
+                parents: (parent1, parent2),
+            });
+            Ok(().into())
+        }
+
+        #[transactional]
+        #[pallet::weight(100)]
+        // 碎片化 kitty：kitty 锁定到金库账户，并铸造份额给所有者
+        pub fn fractionalize(
+            origin: OriginFor<T>,
+            kitty_id: T::Hash,
+            shares: ShareBalanceOf<T>,
+            buyout_price: Option<BalanceOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            // 确保 kitty id 存在，并且归所有者 所有
+            ensure!(Self::is_kitty_owner(&sender, &kitty_id)?, <Error<T>>::NotKittyOwner);
+            Self::ensure_not_blacklisted(&kitty_id)?;
+            // 收购后旧的碎片化信息保留到所有份额持有人领取收购款，避免被覆盖
+            ensure!(!<Fractions<T>>::contains_key(&kitty_id), <Error<T>>::AlreadyFractionalized);
+            ensure!(!shares.is_zero(), <Error<T>>::ZeroShares);
+
+            // 分配份额资产 id
+            let asset_id = Self::next_share_asset_id()?;
+
+            // 创建份额资产，并把全部份额铸造给所有者
+            let vault = Self::vault_account(&kitty_id);
+            T::ShareAssets::create(asset_id, vault.clone(), false, One::one())?;
+            T::ShareAssets::mint_into(asset_id, &sender, shares)?;
+
+            // kitty 锁定到金库账户，质押仍由原所有者承担
+            let kitty = <Kitties<T>>::get(&kitty_id).ok_or(<Error<T>>::KittyNotExist)?;
+            Self::move_kitty(&kitty_id, kitty, &vault)?;
+
+            <Fractions<T>>::insert(&kitty_id, Fraction::<T> {
+                asset_id,
+                shares,
+                curator: sender.clone(),
+                buyout_price,
+                bought_out: false,
+            });
+            Self::deposit_event(Event::Fractionalized { owner: sender, kitty_id, asset_id, shares });
+            Ok(().into())
+        }
+
+        #[transactional]
+        #[pallet::weight(100)]
+        // 持有全部份额的账户销毁份额，赎回 kitty
+        pub fn redeem(origin: OriginFor<T>, kitty_id: T::Hash) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            ensure!(!<MarketplacePaused<T>>::get(), <Error<T>>::MarketplacePaused);
+            Self::ensure_not_blacklisted(&kitty_id)?;
+            let fraction = <Fractions<T>>::get(&kitty_id).ok_or(<Error<T>>::NotFractionalized)?;
+            ensure!(!fraction.bought_out, <Error<T>>::AlreadyBoughtOut);
+            // 确保持有全部份额
+            let total = T::ShareAssets::total_issuance(fraction.asset_id);
+            ensure!(T::ShareAssets::balance(fraction.asset_id, &sender) == total, <Error<T>>::InsufficientShares);
+            T::ShareAssets::burn_from(fraction.asset_id, &sender, total)?;
+
+            // kitty 从金库转给赎回人
+            Self::release_from_vault(&kitty_id, &fraction, &sender)?;
+            <Fractions<T>>::remove(&kitty_id);
+            Self::deposit_event(Event::Redeemed { owner: sender, kitty_id });
+            Ok(().into())
+        }
+
+        #[transactional]
+        #[pallet::weight(100)]
+        // 按收购价整体收购碎片化的 kitty，收购款存入金库等待份额持有人领取
+        pub fn buyout(origin: OriginFor<T>, kitty_id: T::Hash) -> DispatchResultWithPostInfo {
+            let buyer = ensure_signed(origin)?;
+            ensure!(!<MarketplacePaused<T>>::get(), <Error<T>>::MarketplacePaused);
+            Self::ensure_not_blacklisted(&kitty_id)?;
+            let mut fraction = <Fractions<T>>::get(&kitty_id).ok_or(<Error<T>>::NotFractionalized)?;
+            ensure!(!fraction.bought_out, <Error<T>>::AlreadyBoughtOut);
+            let price = fraction.buyout_price.ok_or(<Error<T>>::BuyoutNotAllowed)?;
+
+            // 确保买方 余额 大于等于 收购价
+            ensure!(T::Currency::free_balance(&buyer) >= price, <Error<T>>::NotEnoughBalance);
+            let vault = Self::vault_account(&kitty_id);
+            T::Currency::transfer(&buyer, &vault, price, ExistenceRequirement::KeepAlive)?;
+
+            // kitty 从金库转给收购人
+            Self::release_from_vault(&kitty_id, &fraction, &buyer)?;
+            fraction.bought_out = true;
+            <Fractions<T>>::insert(&kitty_id, fraction);
+            Self::deposit_event(Event::BoughtOut { buyer, kitty_id, price });
+            Ok(().into())
+        }
+
+        #[transactional]
+        #[pallet::weight(100)]
+        // 收购完成后，份额持有人销毁份额并按比例领取收购款
+        pub fn claim_proceeds(origin: OriginFor<T>, kitty_id: T::Hash) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            let fraction = <Fractions<T>>::get(&kitty_id).ok_or(<Error<T>>::NotFractionalized)?;
+            ensure!(fraction.bought_out, <Error<T>>::NotBoughtOut);
+
+            let shares = T::ShareAssets::balance(fraction.asset_id, &sender);
+            ensure!(!shares.is_zero(), <Error<T>>::NoShares);
+            let total = T::ShareAssets::total_issuance(fraction.asset_id);
+
+            // 按份额比例计算收购款
+            let vault = Self::vault_account(&kitty_id);
+            let pot = T::Currency::free_balance(&vault);
+            let proceeds: BalanceOf<T> = if shares == total {
+                pot
+            } else {
+                Perquintill::from_rational(shares.saturated_into::<u128>(), total.saturated_into::<u128>())
+                    .mul_floor(pot)
+            };
+
+            T::ShareAssets::burn_from(fraction.asset_id, &sender, shares)?;
+            T::Currency::transfer(&vault, &sender, proceeds, ExistenceRequirement::AllowDeath)?;
+            // 最后一位份额持有人领取后，清除碎片化信息
+            if shares == total {
+                <Fractions<T>>::remove(&kitty_id);
+            }
+            Self::deposit_event(Event::ProceedsClaimed { who: sender, kitty_id, shares, proceeds });
+            Ok(().into())
+        }
+
+        #[pallet::weight(100)]
+        // 管理员强制转移 kitty，质押随 kitty 转移。碎片化中的 kitty 由份额持有人共有，不能强制转移
+        pub fn force_transfer(origin: OriginFor<T>, kitty_id: T::Hash, to: T::AccountId) -> DispatchResultWithPostInfo {
+            T::ForceOrigin::ensure_origin(origin)?;
+            ensure!(!<Fractions<T>>::contains_key(&kitty_id), <Error<T>>::KittyFractionalized);
+            let from = <Kitties<T>>::get(&kitty_id).ok_or(<Error<T>>::KittyNotExist)?.owner;
+            ensure!(from != to, <Error<T>>::TransferToSelf);
+            Self::transfer_kitty_to(&kitty_id, &to)?;
+            Self::deposit_event(Event::ForceTransferred { from, to, kitty_id });
+            Ok(().into())
+        }
+
+        #[pallet::weight(100)]
+        // 暂停或恢复市场
+        pub fn pause_marketplace(origin: OriginFor<T>, paused: bool) -> DispatchResultWithPostInfo {
+            T::ForceOrigin::ensure_origin(origin)?;
+            <MarketplacePaused<T>>::put(paused);
+            Self::deposit_event(Event::MarketplacePausedSet { paused });
+            Ok(().into())
+        }
+
+        #[pallet::weight(100)]
+        // 暂停或恢复繁殖
+        pub fn pause_breeding(origin: OriginFor<T>, paused: bool) -> DispatchResultWithPostInfo {
+            T::ForceOrigin::ensure_origin(origin)?;
+            <BreedingPaused<T>>::put(paused);
+            Self::deposit_event(Event::BreedingPausedSet { paused });
+            Ok(().into())
+        }
+
+        #[pallet::weight(100)]
+        // 拉黑或解除拉黑 kitty，被拉黑的 kitty 不能定价、交易、购买、繁殖、碎片化
+        pub fn blacklist_kitty(origin: OriginFor<T>, kitty_id: T::Hash, blacklisted: bool) -> DispatchResultWithPostInfo {
+            T::ForceOrigin::ensure_origin(origin)?;
+            ensure!(<Kitties<T>>::contains_key(&kitty_id), <Error<T>>::KittyNotExist);
+            if blacklisted {
+                <Blacklist<T>>::insert(&kitty_id, ());
+            } else {
+                <Blacklist<T>>::remove(&kitty_id);
+            }
+            Self::deposit_event(Event::BlacklistSet { kitty_id, blacklisted });
+            Ok(().into())
+        }
+
+        #[pallet::weight(100)]
+        // 单独设置账户的最大持有数量，None 表示恢复为 MaxKittyOwned
+        pub fn set_max_owned_override(origin: OriginFor<T>, account: T::AccountId, max_owned: Option<u32>) -> DispatchResultWithPostInfo {
+            T::ForceOrigin::ensure_origin(origin)?;
+            match max_owned {
+                Some(max) => {
+                    ensure!(max <= T::MaxKittyOwned::get(), <Error<T>>::MaxOwnedOverrideTooHigh);
+                    <MaxOwnedOverride<T>>::insert(&account, max);
+                },
+                None => <MaxOwnedOverride<T>>::remove(&account),
+            }
+            Self::deposit_event(Event::MaxOwnedOverrideSet { account, max_owned });
             Ok(().into())
         }
 
@@ -226,7 +576,10 @@ pub mod pallet {
         }
 
         // 制造 kitty
-        fn mint(owner: &T::AccountId, dna: Option<[u8; 16]>, gender: Option<Gender>) -> Result<T::Hash, Error<T>> {
+        fn mint(owner: &T::AccountId, dna: Option<[u8; 16]>, gender: Option<Gender>) -> Result<(T::Hash, Kitty<T>), Error<T>> {
+            // 质押费用 随持有数量增加
+            let held = <KittiesOwned<T>>::decode_len(owner).unwrap_or_default() as u32;
+            let deposit = Self::deposit_for(held);
 
             // 初始化kitty 结构体
             let kitty = Kitty::<T>{
@@ -234,24 +587,25 @@ pub mod pallet {
                 price: None,
                 gender: gender.unwrap_or_else(Self::gen_gender),
                 owner: owner.clone(),
+                deposit,
             };
             // hash散列 生成kitty id
             let kitty_id = T::Hashing::hash_of(&kitty);
             // 质押资产
-            let deposit = T::ReservationFee::get();
-            T::ReservableCurrency::reserve(owner, deposit).map_err(|_|<Error<T>>::ReserveBalanceFailed)?;
+            T::ReservableCurrency::reserve_named(&T::ReserveId::get(), owner, deposit).map_err(|_|<Error<T>>::ReserveBalanceFailed)?;
             // 检验是否超过最大值
             let count = <CountKitties<T>>::get().checked_add(1).ok_or(<Error<T>>::CountForKittiesOverflow)?;
             // 确保没有相同的kitty id 存在
             ensure!(<Kitties<T>>::get(&kitty_id) == None, <Error<T>>::KittyExists);
             // 确保 所有者的 kitty 数量没有超过最大值
             <KittiesOwned<T>>::try_mutate(owner, |v| {
-                v.try_push(kitty_id)
-            }).map_err(|_|<Error<T>>::ExceedMaxKittyOwned)?;
+                Self::push_owned(owner, v, kitty_id)
+            })?;
             // 插入
-            <Kitties<T>>::insert(kitty_id, kitty);
+            <Kitties<T>>::insert(kitty_id, kitty.clone());
             <CountKitties<T>>::put(count);
-            Ok(kitty_id)
+            T::OnKittyChange::on_mint(owner, &kitty_id);
+            Ok((kitty_id, kitty))
         }
 
         // 判断 kitty id 是非存在 
@@ -269,17 +623,37 @@ pub mod pallet {
             // 确保 kitty 存在
             let mut kitty = <Kitties<T>>::get(&kitty_id).ok_or(<Error<T>>::KittyNotExist)?;
 
-            // 质押转移
-            let deposit = T::ReservationFee::get();
-            T::ReservableCurrency::reserve(to, deposit).map_err(|_|<Error<T>>::ReserveBalanceFailed)?;
-            _ = T::ReservableCurrency::unreserve(&kitty.owner, deposit);
+            // 质押随 kitty 转移，接收方不需要额外质押
+            kitty.deposit = Self::move_deposit(&kitty.owner, to, kitty.deposit)?;
+            Self::move_kitty(kitty_id, kitty, to)?;
+            Ok(().into())
+        }
 
+        // 持有 held 只 kitty 的账户，新 kitty 的质押费用
+        pub fn deposit_for(held: u32) -> ReserveBalanceOf<T> {
+            T::ReservationFee::get().saturating_add(T::ReservationFeeStep::get().saturating_mul(held.into()))
+        }
+
+        // 命名质押从原账户转移到新账户，返回实际转移的质押
+        fn move_deposit(from: &T::AccountId, to: &T::AccountId, deposit: ReserveBalanceOf<T>) -> Result<ReserveBalanceOf<T>, Error<T>> {
+            let remaining = T::ReservableCurrency::repatriate_reserved_named(
+                &T::ReserveId::get(),
+                from,
+                to,
+                deposit,
+                BalanceStatus::Reserved,
+            ).map_err(|_|<Error<T>>::ReserveBalanceFailed)?;
+            Ok(deposit.saturating_sub(remaining))
+        }
+
+        // 修改 kitty 的所有者，不涉及质押
+        fn move_kitty(kitty_id: &T::Hash, mut kitty: Kitty<T>, to: &T::AccountId) -> Result<(), Error<T>> {
             // 确保 持有者的kitty 数量减少
             <KittiesOwned<T>>::try_mutate(kitty.owner.clone(), |v| {
                 match v.iter().position(|&id| id == *kitty_id) {
                     Some(index) => {
                         v.swap_remove(index); 
-                        Ok(().into())
+                        Ok(())
                     }
                     None => Err(<Error<T>>::KittyNotExist),
                 }
@@ -287,15 +661,58 @@ pub mod pallet {
 
             // 确保 交易方 kitty 数量增加
             <KittiesOwned<T>>::try_mutate(to, |v| {
-                v.try_push(*kitty_id)
-            }).map_err(|_|<Error<T>>::ExceedMaxKittyOwned)?;
+                Self::push_owned(to, v, *kitty_id)
+            })?;
 
-            kitty.owner = to.clone();
+            let from = core::mem::replace(&mut kitty.owner, to.clone());
             kitty.price = None;
 
             // 插入kitty
             <Kitties<T>>::insert(&kitty_id, kitty);
-            Ok(().into())
+            T::OnKittyChange::on_transfer(&from, to, kitty_id);
+            Ok(())
+        }
+
+        // 账户的最大持有数量
+        pub fn max_owned(who: &T::AccountId) -> u32 {
+            <MaxOwnedOverride<T>>::get(who).unwrap_or_else(T::MaxKittyOwned::get)
+        }
+
+        // 加入所有者的 kitty 集合，确保不超过最大持有数量
+        fn push_owned(who: &T::AccountId, owned: &mut BoundedVec<T::Hash, T::MaxKittyOwned>, kitty_id: T::Hash) -> Result<(), Error<T>> {
+            ensure!((owned.len() as u32) < Self::max_owned(who), <Error<T>>::ExceedMaxKittyOwned);
+            owned.try_push(kitty_id).map_err(|_|<Error<T>>::ExceedMaxKittyOwned)
+        }
+
+        // 确保 kitty 没有被拉黑
+        fn ensure_not_blacklisted(kitty_id: &T::Hash) -> Result<(), Error<T>> {
+            ensure!(!<Blacklist<T>>::contains_key(kitty_id), <Error<T>>::KittyBlacklisted);
+            Ok(())
+        }
+
+        // 碎片化 kitty 的金库账户
+        pub fn vault_account(kitty_id: &T::Hash) -> T::AccountId {
+            T::PalletId::get().into_sub_account_truncating(kitty_id)
+        }
+
+        // 分配下一个空闲的份额资产 id，跳过已被其他人创建的资产。
+        // 资产创建时最小余额不能为 0，所以最小余额为 0 表示该 id 未被占用。
+        // 跳过的 id 不会再被检查，总检查次数不超过已创建的资产数量
+        fn next_share_asset_id() -> Result<T::ShareAssetId, Error<T>> {
+            let mut asset_id = <NextShareAssetId<T>>::get();
+            while !T::ShareAssets::minimum_balance(asset_id).is_zero() {
+                asset_id = asset_id.checked_add(&One::one()).ok_or(<Error<T>>::ShareAssetIdOverflow)?;
+            }
+            let next_asset_id = asset_id.checked_add(&One::one()).ok_or(<Error<T>>::ShareAssetIdOverflow)?;
+            <NextShareAssetId<T>>::put(next_asset_id);
+            Ok(asset_id)
+        }
+
+        // kitty 从金库转出，质押从碎片化发起人转给新所有者
+        fn release_from_vault(kitty_id: &T::Hash, fraction: &Fraction<T>, to: &T::AccountId) -> Result<(), Error<T>> {
+            let mut kitty = <Kitties<T>>::get(kitty_id).ok_or(<Error<T>>::KittyNotExist)?;
+            kitty.deposit = Self::move_deposit(&fraction.curator, to, kitty.deposit)?;
+            Self::move_kitty(kitty_id, kitty, to)
         }
 
         // 繁殖 dna
@@ -0,0 +1,202 @@
+// kitty 存储迁移
+use super::*;
+use core::marker::PhantomData;
+use frame_support::{
+    log,
+    sp_runtime::traits::{Saturating, Zero},
+    sp_std::vec::Vec,
+    traits::{Get, GetStorageVersion, NamedReservableCurrency, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+};
+
+pub mod v1 {
+    use super::*;
+    use codec::{Decode, Encode};
+
+    // v0 版本的 kitty 结构体，没有 deposit 字段，质押为匿名质押
+    #[derive(Encode, Decode)]
+    pub struct OldKitty<AccountId, Balance> {
+        pub dna: [u8; 16],
+        pub price: Option<Balance>,
+        pub gender: Gender,
+        pub owner: AccountId,
+    }
+
+    // 把 v0 的匿名固定质押 OldFee 重新质押为按持有数量计算的命名质押，
+    // 并把质押金额记录到 kitty 中
+    pub struct MigrateToV1<T, OldFee>(PhantomData<(T, OldFee)>);
+
+    impl<T: Config, OldFee: Get<ReserveBalanceOf<T>>> OnRuntimeUpgrade for MigrateToV1<T, OldFee> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 1 {
+                log::info!(target: "runtime::kitty", "skipping v1 migration, on-chain version is {:?}", on_chain);
+                return T::DbWeight::get().reads(1)
+            }
+
+            let mut translated = 0u64;
+            <Kitties<T>>::translate::<OldKitty<T::AccountId, BalanceOf<T>>, _>(|kitty_id, old| {
+                translated += 1;
+                // 释放旧的匿名质押
+                _ = T::ReservableCurrency::unreserve(&old.owner, OldFee::get());
+                // 按 kitty 在所有者列表中的位置计算新的质押
+                let held = <KittiesOwned<T>>::get(&old.owner)
+                    .iter()
+                    .position(|id| *id == kitty_id)
+                    .unwrap_or_default() as u32;
+                let deposit = Pallet::<T>::deposit_for(held);
+                let deposit = match T::ReservableCurrency::reserve_named(&T::ReserveId::get(), &old.owner, deposit) {
+                    Ok(()) => deposit,
+                    Err(_) => {
+                        log::warn!(target: "runtime::kitty", "unable to re-reserve deposit for kitty {:?}", kitty_id);
+                        Zero::zero()
+                    },
+                };
+                Some(Kitty::<T> {
+                    dna: old.dna,
+                    price: old.price,
+                    gender: old.gender,
+                    owner: old.owner,
+                    deposit,
+                })
+            });
+
+            StorageVersion::new(1).put::<Pallet<T>>();
+            <AppliedReservationFees<T>>::put((T::ReservationFee::get(), T::ReservationFeeStep::get()));
+            log::info!(target: "runtime::kitty", "migrated {} kitties to v1", translated);
+            // 每只 kitty: 读写 kitty、读所有者列表、读写质押两次
+            T::DbWeight::get().reads_writes(translated * 4 + 1, translated * 3 + 2)
+        }
+
+        // 同一次升级的所有迁移先统一执行 pre_upgrade，最后统一执行 post_upgrade，
+        // 因此只在需要迁移时检查，并使用本迁移独有的临时存储键
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<(), &'static str> {
+            use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+            if Pallet::<T>::on_chain_storage_version() < 1 {
+                Self::set_temp_storage(<Kitties<T>>::iter_keys().count() as u32, "kitty_v1_count");
+            }
+            Ok(())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+            let count: u32 = match Self::get_temp_storage("kitty_v1_count") {
+                Some(count) => count,
+                None => return Ok(()),
+            };
+            frame_support::ensure!(Pallet::<T>::on_chain_storage_version() >= 1, "kitty storage version must be at least 1");
+            // 所有 kitty 都能按新结构体解码
+            frame_support::ensure!(<Kitties<T>>::iter_values().count() as u32 == count, "kitties lost in migration");
+            Ok(())
+        }
+    }
+}
+
+// 治理调整 ReservationFee 或 ReservationFeeStep 后，随运行时升级执行，
+// 按新的费用曲线重新计算并质押所有 kitty 的质押。碎片化中的 kitty 保持不变。
+// 升级时只记录新的费用曲线并开始重新质押，实际的重新质押在之后每个区块的 on_idle 中分批完成，
+// 不会超过区块权重。费用曲线与 AppliedReservationFees 相同时不做任何事，因此可以一直留在运行时的迁移列表中
+pub struct ReReserveDeposits<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for ReReserveDeposits<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let fees = (T::ReservationFee::get(), T::ReservationFeeStep::get());
+        if <AppliedReservationFees<T>>::get() == Some(fees) {
+            log::info!(target: "runtime::kitty", "skipping re-reserving deposits, fees are unchanged");
+            return T::DbWeight::get().reads(1)
+        }
+
+        // 上一轮没有完成时从头开始，已经按新曲线质押的 kitty 不会再改变
+        <AppliedReservationFees<T>>::put(fees);
+        <ReReserveProgress<T>>::put(None::<T::AccountId>);
+        log::info!(target: "runtime::kitty", "re-reserving deposits for the new fees in on_idle");
+        T::DbWeight::get().reads_writes(1, 2)
+    }
+}
+
+// 重新质押一个账户所需的最大权重
+fn re_reserve_owner_weight<T: Config>() -> Weight {
+    let max = T::MaxKittyOwned::get() as u64;
+    // 读所有者列表；每只 kitty: 读碎片化信息和 kitty，写 kitty 和质押
+    T::DbWeight::get().reads_writes(1 + 2 * max, 2 * max)
+}
+
+// 在 remaining_weight 内继续重新质押，返回使用的权重
+pub(crate) fn re_reserve_step<T: Config>(remaining_weight: Weight) -> Weight {
+    let mut used = T::DbWeight::get().reads(1);
+    if used > remaining_weight {
+        return 0
+    }
+    let mut last = match <ReReserveProgress<T>>::get() {
+        Some(last) => last,
+        None => return used,
+    };
+    let per_owner = re_reserve_owner_weight::<T>();
+    used = used.saturating_add(T::DbWeight::get().writes(1));
+    if used.saturating_add(per_owner) > remaining_weight {
+        return T::DbWeight::get().reads(1)
+    }
+
+    let mut owners = match &last {
+        Some(owner) => <KittiesOwned<T>>::iter_from(<KittiesOwned<T>>::hashed_key_for(owner)),
+        None => <KittiesOwned<T>>::iter(),
+    };
+    loop {
+        if used.saturating_add(per_owner) > remaining_weight {
+            <ReReserveProgress<T>>::put(last);
+            return used
+        }
+        match owners.next() {
+            Some((owner, kitty_ids)) => {
+                used = used.saturating_add(per_owner);
+                re_reserve_owner::<T>(&owner, kitty_ids.into_inner());
+                last = Some(owner);
+            },
+            None => {
+                <ReReserveProgress<T>>::kill();
+                log::info!(target: "runtime::kitty", "re-reserved all kitty deposits");
+                return used
+            },
+        }
+    }
+}
+
+// 按当前费用曲线重新质押 owner 的 kitty
+fn re_reserve_owner<T: Config>(owner: &T::AccountId, kitty_ids: Vec<T::Hash>) {
+    let id = T::ReserveId::get();
+    let mut kitties = Vec::new();
+    for kitty_id in kitty_ids {
+        if <Fractions<T>>::contains_key(&kitty_id) {
+            continue
+        }
+        if let Some(kitty) = <Kitties<T>>::get(&kitty_id) {
+            kitties.push((kitty_id, kitty));
+        }
+    }
+    // 按原质押从低到高重新分配新曲线上的质押，与 KittiesOwned 中的顺序无关
+    kitties.sort_by(|(a_id, a), (b_id, b)| (a.deposit, a_id).cmp(&(b.deposit, b_id)));
+
+    for (held, (kitty_id, mut kitty)) in kitties.into_iter().enumerate() {
+        let target = Pallet::<T>::deposit_for(held as u32);
+        if target > kitty.deposit {
+            // 余额不足时保持原质押
+            if T::ReservableCurrency::reserve_named(&id, owner, target - kitty.deposit).is_err() {
+                log::warn!(target: "runtime::kitty", "unable to re-reserve deposit for kitty {:?}", kitty_id);
+                continue
+            }
+            kitty.deposit = target;
+        } else if target < kitty.deposit {
+            // 只记录实际释放的质押
+            let missing = T::ReservableCurrency::unreserve_named(&id, owner, kitty.deposit - target);
+            if !missing.is_zero() {
+                log::warn!(target: "runtime::kitty", "unable to release the whole deposit of kitty {:?}", kitty_id);
+            }
+            kitty.deposit = target.saturating_add(missing);
+        } else {
+            continue
+        }
+        <Kitties<T>>::insert(kitty_id, kitty);
+    }
+}
@@ -1,7 +1,9 @@
 use crate as pallet_kitty;
-use frame_support::traits::{ConstU16, ConstU32, ConstU64, ConstU128, OnFinalize, OnInitialize};
+use frame_support::{traits::{ConstU16, ConstU32, ConstU64, ConstU128, OnFinalize, OnInitialize}, PalletId};
+use frame_system::EnsureRoot;
 use frame_system as system;
 use sp_core::H256;
+use std::cell::RefCell;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
@@ -22,6 +24,7 @@ frame_support::construct_runtime!(
 		KittyModule: pallet_kitty::{Pallet, Call, Storage, Event<T>},
         Randomness: pallet_randomness_collective_flip::{Pallet, Storage},
         Balancess: pallet_balances::{Pallet, Call, Storage, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -29,7 +32,7 @@ impl system::Config for Test {
 	type BaseCallFilter = frame_support::traits::Everything;
 	type BlockWeights = ();
 	type BlockLength = ();
-	type DbWeight = ();
+	type DbWeight = frame_support::weights::constants::RocksDbWeight;
 	type Origin = Origin;
 	type Call = Call;
 	type Index = u64;
@@ -58,7 +61,52 @@ impl pallet_kitty::Config for Test {
     type Randomness = Randomness;
     type MaxKittyOwned = ConstU32<3>;
     type ReservableCurrency = Balancess;
-    type ReservationFee = ConstU128<100>;
+    type ReserveId = KittyReserveId;
+    type ReservationFee = ConstU128<50>;
+    type ReservationFeeStep = ConstU128<50>;
+    type OnKittyChange = KittyChangeRecorder;
+    type PalletId = KittyPalletId;
+    type ShareAssetId = u32;
+    type ShareAssets = Assets;
+    type ForceOrigin = EnsureRoot<u64>;
+}
+
+frame_support::parameter_types! {
+    pub const KittyPalletId: PalletId = PalletId(*b"py/kitty");
+    pub const KittyReserveId: [u8; 8] = *b"kitty/dp";
+}
+
+// kitty 回调记录
+#[derive(Clone, Debug, PartialEq)]
+pub enum KittyChange {
+    Mint(u64, H256),
+    Transfer(u64, u64, H256),
+    Breed(u64, H256, (H256, H256)),
+}
+
+thread_local! {
+    pub static KITTY_CHANGES: RefCell<Vec<KittyChange>> = RefCell::new(vec![]);
+}
+
+pub struct KittyChangeRecorder;
+impl pallet_kitty::OnKittyChange<u64, H256> for KittyChangeRecorder {
+    fn on_mint(owner: &u64, kitty_id: &H256) {
+        KITTY_CHANGES.with(|c| c.borrow_mut().push(KittyChange::Mint(*owner, *kitty_id)));
+    }
+
+    fn on_transfer(from: &u64, to: &u64, kitty_id: &H256) {
+        KITTY_CHANGES.with(|c| c.borrow_mut().push(KittyChange::Transfer(*from, *to, *kitty_id)));
+    }
+
+    fn on_breed(owner: &u64, kitty_id: &H256, parents: (&H256, &H256)) {
+        KITTY_CHANGES.with(|c| {
+            c.borrow_mut().push(KittyChange::Breed(*owner, *kitty_id, (*parents.0, *parents.1)))
+        });
+    }
+}
+
+pub fn kitty_changes() -> Vec<KittyChange> {
+    KITTY_CHANGES.with(|c| c.borrow().clone())
 }
 
 impl pallet_randomness_collective_flip::Config for Test {}
@@ -72,15 +120,32 @@ impl pallet_balances::Config for Test {
     type AccountStore = System;
     type WeightInfo = ();
     type MaxLocks = ConstU32<50>;
-    type MaxReserves = ();
-    type ReserveIdentifier = ();
+    type MaxReserves = ConstU32<50>;
+    type ReserveIdentifier = [u8; 8];
+}
+
+impl pallet_assets::Config for Test {
+    type Event = Event;
+    type Balance = u64;
+    type AssetId = u32;
+    type Currency = Balancess;
+    type ForceOrigin = EnsureRoot<u64>;
+    type AssetDeposit = ConstU128<0>;
+    type AssetAccountDeposit = ConstU128<0>;
+    type MetadataDepositBase = ConstU128<0>;
+    type MetadataDepositPerByte = ConstU128<0>;
+    type ApprovalDeposit = ConstU128<0>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
     pallet_balances::GenesisConfig::<Test>{
-        balances: vec![(1, 200), (2, 500)]
+        balances: vec![(1, 200), (2, 500), (3, 1000)]
     }.assimilate_storage(&mut t).unwrap();
     let mut ext = sp_io::TestExternalities::new(t);
     ext.execute_with(|| System::set_block_number(1));
@@ -1,5 +1,5 @@
 use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok, assert_err};
+use frame_support::{assert_noop, assert_ok, assert_err, traits::NamedReservableCurrency};
 
 #[test]
 fn test_create_kitty() {
@@ -79,13 +79,14 @@ fn test_buy_kitty() {
         assert_err!(KittyModule::buy_kitty(Origin::signed(1), kitty_id, 50), <Error<Test>>::KittyBidPriceTooLow);
         // 余额不足
         assert_err!(KittyModule::buy_kitty(Origin::signed(1), kitty_id, 300), <Error<Test>>::NotEnoughBalance);
-        // 没有余额质押资产
-        assert_err!(KittyModule::buy_kitty(Origin::signed(1), kitty_id, 120), <Error<Test>>::ReserveBalanceFailed);
-        // 成功购买
-        assert_ok!(KittyModule::buy_kitty(Origin::signed(1), kitty_id, 100));
+        // 成功购买, 买家不需要额外质押
+        assert_ok!(KittyModule::buy_kitty(Origin::signed(1), kitty_id, 190));
         // 校验所有权
         let kitty_id = super::pallet::KittiesOwned::<Test>::get(1)[0];
         assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id).unwrap().owner, 1);
+        // 质押随 kitty 转移
+        assert_eq!(Balancess::reserved_balance(1), 50);
+        assert_eq!(Balancess::reserved_balance(2), 0);
     })
 }
 
@@ -107,5 +108,300 @@ fn test_breed_kitty() {
         // 检查新生kitty 是否属于账户2
         let kitty_id_owner2_3 = super::pallet::KittiesOwned::<Test>::get(2)[2];
         assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id_owner2_3).unwrap().owner, 2);
+        // 繁殖事件包含 dna、性别和父母
+        let kitty = super::pallet::Kitties::<Test>::get(&kitty_id_owner2_3).unwrap();
+        System::assert_last_event(Event::KittyModule(crate::Event::Bred {
+            owner: 2,
+            kitty_id: kitty_id_owner2_3,
+            dna: kitty.dna,
+            gender: kitty.gender,
+            parents: (kitty_id_owner2_1, kitty_id_owner2_2),
+        }));
+    })
+}
+
+#[test]
+fn test_on_kitty_change() {
+    new_test_ext().execute_with(|| {
+        // 创建两个kitty
+        assert_ok!(KittyModule::create_kitty(Origin::signed(2)));
+        run_to_block(2);
+        assert_ok!(KittyModule::create_kitty(Origin::signed(2)));
+        let parent1 = super::pallet::KittiesOwned::<Test>::get(2)[0];
+        let parent2 = super::pallet::KittiesOwned::<Test>::get(2)[1];
+        // 繁殖
+        assert_ok!(KittyModule::breed_kitty(Origin::signed(2), parent1, parent2));
+        let child = super::pallet::KittiesOwned::<Test>::get(2)[2];
+        // 交易
+        assert_ok!(KittyModule::transfer(Origin::signed(2), parent1, 1));
+        // 失败的交易不会触发回调
+        assert_noop!(KittyModule::transfer(Origin::signed(2), parent1, 1), <Error<Test>>::NotKittyOwner);
+
+        assert_eq!(kitty_changes(), vec![
+            KittyChange::Mint(2, parent1),
+            KittyChange::Mint(2, parent2),
+            KittyChange::Mint(2, child),
+            KittyChange::Breed(2, child, (parent1, parent2)),
+            KittyChange::Transfer(2, 1, parent1),
+        ]);
+    })
+}
+
+#[test]
+fn test_fractionalize_and_redeem() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittyModule::create_kitty(Origin::signed(2)));
+        let kitty_id = super::pallet::KittiesOwned::<Test>::get(2)[0];
+        // 只有所有者可以碎片化
+        assert_err!(KittyModule::fractionalize(Origin::signed(1), kitty_id, 100, None), <Error<Test>>::NotKittyOwner);
+        assert_err!(KittyModule::fractionalize(Origin::signed(2), kitty_id, 0, None), <Error<Test>>::ZeroShares);
+        assert_ok!(KittyModule::fractionalize(Origin::signed(2), kitty_id, 100, None));
+
+        // kitty 锁定在金库账户，份额属于原所有者
+        let vault = KittyModule::vault_account(&kitty_id);
+        assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id).unwrap().owner, vault);
+        assert_eq!(super::pallet::KittiesOwned::<Test>::get(2).len(), 0);
+        let asset_id = super::pallet::Fractions::<Test>::get(&kitty_id).unwrap().asset_id;
+        assert_eq!(Assets::balance(asset_id, 2), 100);
+        // 金库中的 kitty 不能再被交易
+        assert_err!(KittyModule::transfer(Origin::signed(2), kitty_id, 1), <Error<Test>>::NotKittyOwner);
+        // 管理员也不能把 kitty 强制移出金库
+        assert_noop!(KittyModule::force_transfer(Origin::root(), kitty_id, 1), <Error<Test>>::KittyFractionalized);
+
+        // 没有全部份额不能赎回
+        assert_ok!(Assets::transfer(Origin::signed(2), asset_id, 3, 40));
+        assert_err!(KittyModule::redeem(Origin::signed(2), kitty_id), <Error<Test>>::InsufficientShares);
+        // 没有设置收购价不能收购
+        assert_err!(KittyModule::buyout(Origin::signed(3), kitty_id), <Error<Test>>::BuyoutNotAllowed);
+
+        // 集齐份额后赎回
+        assert_ok!(Assets::transfer(Origin::signed(2), asset_id, 3, 60));
+        // 市场暂停或 kitty 被拉黑时不能赎回
+        assert_ok!(KittyModule::pause_marketplace(Origin::root(), true));
+        assert_noop!(KittyModule::redeem(Origin::signed(3), kitty_id), <Error<Test>>::MarketplacePaused);
+        assert_ok!(KittyModule::pause_marketplace(Origin::root(), false));
+        assert_ok!(KittyModule::blacklist_kitty(Origin::root(), kitty_id, true));
+        assert_noop!(KittyModule::redeem(Origin::signed(3), kitty_id), <Error<Test>>::KittyBlacklisted);
+        assert_ok!(KittyModule::blacklist_kitty(Origin::root(), kitty_id, false));
+        assert_ok!(KittyModule::redeem(Origin::signed(3), kitty_id));
+        assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id).unwrap().owner, 3);
+        assert_eq!(super::pallet::KittiesOwned::<Test>::get(3)[0], kitty_id);
+        assert_eq!(Assets::total_supply(asset_id), 0);
+        assert!(super::pallet::Fractions::<Test>::get(&kitty_id).is_none());
+        // 质押从账户2 转到 账户3
+        assert_eq!(Balancess::reserved_balance(2), 0);
+        assert_eq!(Balancess::reserved_balance(3), 50);
+    })
+}
+
+#[test]
+fn test_fractionalize_skips_taken_asset_ids() {
+    new_test_ext().execute_with(|| {
+        // 其他人抢先创建了 id 0 和 1 的资产
+        assert_ok!(Assets::create(Origin::signed(1), 0, 1, 1));
+        assert_ok!(Assets::force_create(Origin::root(), 1, 1, true, 1));
+
+        assert_ok!(KittyModule::create_kitty(Origin::signed(2)));
+        let kitty_id = super::pallet::KittiesOwned::<Test>::get(2)[0];
+        assert_ok!(KittyModule::fractionalize(Origin::signed(2), kitty_id, 100, None));
+        assert_eq!(super::pallet::Fractions::<Test>::get(&kitty_id).unwrap().asset_id, 2);
+        assert_eq!(Assets::balance(2, 2), 100);
+        assert_eq!(super::pallet::NextShareAssetId::<Test>::get(), 3);
+    })
+}
+
+#[test]
+fn test_fractionalize_buyout() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittyModule::create_kitty(Origin::signed(2)));
+        let kitty_id = super::pallet::KittiesOwned::<Test>::get(2)[0];
+        assert_ok!(KittyModule::fractionalize(Origin::signed(2), kitty_id, 100, Some(300)));
+        let asset_id = super::pallet::Fractions::<Test>::get(&kitty_id).unwrap().asset_id;
+        assert_ok!(Assets::transfer(Origin::signed(2), asset_id, 1, 25));
+
+        // 收购前不能领取收购款
+        assert_err!(KittyModule::claim_proceeds(Origin::signed(1), kitty_id), <Error<Test>>::NotBoughtOut);
+        // 市场暂停或 kitty 被拉黑时不能收购
+        assert_ok!(KittyModule::pause_marketplace(Origin::root(), true));
+        assert_noop!(KittyModule::buyout(Origin::signed(3), kitty_id), <Error<Test>>::MarketplacePaused);
+        assert_ok!(KittyModule::pause_marketplace(Origin::root(), false));
+        assert_ok!(KittyModule::blacklist_kitty(Origin::root(), kitty_id, true));
+        assert_noop!(KittyModule::buyout(Origin::signed(3), kitty_id), <Error<Test>>::KittyBlacklisted);
+        assert_ok!(KittyModule::blacklist_kitty(Origin::root(), kitty_id, false));
+        // 按收购价收购
+        assert_ok!(KittyModule::buyout(Origin::signed(3), kitty_id));
+        assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id).unwrap().owner, 3);
+        assert_err!(KittyModule::buyout(Origin::signed(3), kitty_id), <Error<Test>>::AlreadyBoughtOut);
+        assert_err!(KittyModule::redeem(Origin::signed(2), kitty_id), <Error<Test>>::AlreadyBoughtOut);
+        // 收购款领取完之前，收购人不能再次碎片化
+        assert_noop!(
+            KittyModule::fractionalize(Origin::signed(3), kitty_id, 10, None),
+            <Error<Test>>::AlreadyFractionalized
+        );
+
+        // 份额持有人按比例领取收购款
+        assert_ok!(KittyModule::claim_proceeds(Origin::signed(1), kitty_id));
+        assert_eq!(Balancess::free_balance(1), 200 + 75);
+        assert_err!(KittyModule::claim_proceeds(Origin::signed(1), kitty_id), <Error<Test>>::NoShares);
+        assert_ok!(KittyModule::claim_proceeds(Origin::signed(2), kitty_id));
+        assert_eq!(Balancess::free_balance(2), 500 - 50 + 225);
+        assert!(super::pallet::Fractions::<Test>::get(&kitty_id).is_none());
+
+        // 全部领取后可以开始新一轮碎片化
+        assert_ok!(KittyModule::fractionalize(Origin::signed(3), kitty_id, 10, None));
+        let new_asset_id = super::pallet::Fractions::<Test>::get(&kitty_id).unwrap().asset_id;
+        assert_ne!(new_asset_id, asset_id);
+        assert_eq!(Assets::balance(new_asset_id, 3), 10);
+    })
+}
+
+#[test]
+fn test_deposit_curve_and_gift() {
+    new_test_ext().execute_with(|| {
+        // 质押随持有数量增加: 50, 100
+        assert_ok!(KittyModule::create_kitty(Origin::signed(1)));
+        run_to_block(2);
+        assert_ok!(KittyModule::create_kitty(Origin::signed(1)));
+        assert_eq!(Balancess::reserved_balance(1), 150);
+        assert_eq!(Balancess::free_balance(1), 50);
+
+        assert_ok!(KittyModule::create_kitty(Origin::signed(2)));
+        run_to_block(3);
+        assert_ok!(KittyModule::create_kitty(Origin::signed(2)));
+        let kitty_id = super::pallet::KittiesOwned::<Test>::get(2)[1];
+        assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id).unwrap().deposit, 100);
+
+        // 赠送给可用余额不足质押的账户
+        assert_ok!(KittyModule::transfer(Origin::signed(2), kitty_id, 1));
+        assert_eq!(Balancess::reserved_balance(1), 250);
+        assert_eq!(Balancess::free_balance(1), 50);
+        assert_eq!(Balancess::reserved_balance(2), 50);
+        assert_eq!(Balancess::reserved_balance_named(&KittyReserveId::get(), &1), 250);
+    })
+}
+
+#[test]
+fn test_migrate_to_v1() {
+    use crate::migrations::v1::{MigrateToV1, OldKitty};
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion};
+    use super::pallet::Gender;
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<KittyModule>();
+        // 写入 v0 版本的 kitty, 匿名质押 100
+        let kitty_id = sp_core::H256::repeat_byte(1);
+        let old = OldKitty::<u64, Balance> { dna: [1; 16], price: Some(10), gender: Gender::Male, owner: 1 };
+        frame_support::storage::unhashed::put(&super::pallet::Kitties::<Test>::hashed_key_for(kitty_id), &old);
+        super::pallet::KittiesOwned::<Test>::insert(1, frame_support::BoundedVec::try_from(vec![kitty_id]).unwrap());
+        assert_ok!(Balancess::reserve(&1, 100));
+
+        frame_support::parameter_types! {
+            pub const OldFee: Balance = 100;
+        }
+        MigrateToV1::<Test, OldFee>::on_runtime_upgrade();
+
+        let kitty = super::pallet::Kitties::<Test>::get(&kitty_id).unwrap();
+        assert_eq!(kitty.deposit, 50);
+        assert_eq!(kitty.price, Some(10));
+        assert_eq!(Balancess::reserved_balance(1), 50);
+        assert_eq!(Balancess::reserved_balance_named(&KittyReserveId::get(), &1), 50);
+        assert_eq!(KittyModule::on_chain_storage_version(), 1);
+    })
+}
+
+#[test]
+fn test_re_reserve_deposits() {
+    use crate::migrations::ReReserveDeposits;
+    use frame_support::{traits::{Get, Hooks, OnRuntimeUpgrade}, weights::{constants::RocksDbWeight, Weight}};
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittyModule::create_kitty(Origin::signed(1)));
+        run_to_block(2);
+        assert_ok!(KittyModule::create_kitty(Origin::signed(1)));
+        let kitty_id1 = super::pallet::KittiesOwned::<Test>::get(1)[0];
+        let kitty_id2 = super::pallet::KittiesOwned::<Test>::get(1)[1];
+
+        // 模拟按旧费用曲线 40, 80 质押，并打乱 KittiesOwned 的顺序
+        super::pallet::AppliedReservationFees::<Test>::put((40, 40));
+        Balancess::unreserve_named(&KittyReserveId::get(), &1, 30);
+        super::pallet::Kitties::<Test>::mutate(&kitty_id1, |kitty| kitty.as_mut().unwrap().deposit = 40);
+        super::pallet::Kitties::<Test>::mutate(&kitty_id2, |kitty| kitty.as_mut().unwrap().deposit = 80);
+        super::pallet::KittiesOwned::<Test>::insert(1, frame_support::BoundedVec::try_from(vec![kitty_id2, kitty_id1]).unwrap());
+        assert_ok!(KittyModule::create_kitty(Origin::signed(2)));
+        let kitty_id3 = super::pallet::KittiesOwned::<Test>::get(2)[0];
+        Balancess::unreserve_named(&KittyReserveId::get(), &2, 10);
+        super::pallet::Kitties::<Test>::mutate(&kitty_id3, |kitty| kitty.as_mut().unwrap().deposit = 40);
+
+        ReReserveDeposits::<Test>::on_runtime_upgrade();
+        // 升级时只开始重新质押，在 on_idle 中按剩余权重分批完成
+        assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id1).unwrap().deposit, 40);
+        assert_eq!(KittyModule::on_idle(2, 0), 0);
+        assert_eq!(super::pallet::ReReserveProgress::<Test>::get(), Some(None));
+        // 剩余权重只够处理一个账户
+        let db = RocksDbWeight::get();
+        let one_owner = db.reads(1) + db.writes(1) + db.reads_writes(7, 6);
+        assert_eq!(KittyModule::on_idle(2, one_owner), one_owner);
+        assert!(matches!(super::pallet::ReReserveProgress::<Test>::get(), Some(Some(_))));
+        KittyModule::on_idle(3, Weight::MAX);
+        assert_eq!(super::pallet::ReReserveProgress::<Test>::get(), None);
+        assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id3).unwrap().deposit, 50);
+        assert_eq!(Balancess::reserved_balance_named(&KittyReserveId::get(), &2), 50);
+        assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id1).unwrap().deposit, 50);
+        assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id2).unwrap().deposit, 100);
+        assert_eq!(Balancess::reserved_balance_named(&KittyReserveId::get(), &1), 150);
+        assert_eq!(super::pallet::AppliedReservationFees::<Test>::get(), Some((50, 50)));
+
+        // 费用曲线不变时不再调整
+        super::pallet::Kitties::<Test>::mutate(&kitty_id1, |kitty| kitty.as_mut().unwrap().deposit = 40);
+        ReReserveDeposits::<Test>::on_runtime_upgrade();
+        KittyModule::on_idle(4, Weight::MAX);
+        assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id1).unwrap().deposit, 40);
+    })
+}
+
+#[test]
+fn test_moderation() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittyModule::create_kitty(Origin::signed(2)));
+        run_to_block(2);
+        assert_ok!(KittyModule::create_kitty(Origin::signed(2)));
+        let kitty_id1 = super::pallet::KittiesOwned::<Test>::get(2)[0];
+        let kitty_id2 = super::pallet::KittiesOwned::<Test>::get(2)[1];
+
+        // 只有管理员可以操作
+        assert_noop!(KittyModule::pause_marketplace(Origin::signed(2), true), sp_runtime::DispatchError::BadOrigin);
+
+        // 暂停市场
+        assert_ok!(KittyModule::pause_marketplace(Origin::root(), true));
+        assert_noop!(KittyModule::set_price(Origin::signed(2), kitty_id1, Some(10)), <Error<Test>>::MarketplacePaused);
+        assert_noop!(KittyModule::buy_kitty(Origin::signed(1), kitty_id1, 20), <Error<Test>>::MarketplacePaused);
+        assert_ok!(KittyModule::pause_marketplace(Origin::root(), false));
+        assert_ok!(KittyModule::set_price(Origin::signed(2), kitty_id1, Some(10)));
+
+        // 暂停繁殖
+        assert_ok!(KittyModule::pause_breeding(Origin::root(), true));
+        assert_noop!(KittyModule::breed_kitty(Origin::signed(2), kitty_id1, kitty_id2), <Error<Test>>::BreedingPaused);
+        assert_ok!(KittyModule::pause_breeding(Origin::root(), false));
+
+        // 拉黑 kitty
+        assert_ok!(KittyModule::blacklist_kitty(Origin::root(), kitty_id1, true));
+        assert_noop!(KittyModule::transfer(Origin::signed(2), kitty_id1, 1), <Error<Test>>::KittyBlacklisted);
+        assert_noop!(KittyModule::buy_kitty(Origin::signed(1), kitty_id1, 20), <Error<Test>>::KittyBlacklisted);
+        assert_noop!(KittyModule::breed_kitty(Origin::signed(2), kitty_id1, kitty_id2), <Error<Test>>::KittyBlacklisted);
+
+        // 管理员强制转移被拉黑的 kitty
+        assert_ok!(KittyModule::force_transfer(Origin::root(), kitty_id1, 1));
+        assert_eq!(super::pallet::Kitties::<Test>::get(&kitty_id1).unwrap().owner, 1);
+        assert_eq!(Balancess::reserved_balance(1), 50);
+
+        // 单独设置最大持有数量
+        assert_noop!(
+            KittyModule::set_max_owned_override(Origin::root(), 1, Some(4)),
+            <Error<Test>>::MaxOwnedOverrideTooHigh
+        );
+        assert_ok!(KittyModule::set_max_owned_override(Origin::root(), 1, Some(1)));
+        assert_noop!(KittyModule::transfer(Origin::signed(2), kitty_id2, 1), <Error<Test>>::ExceedMaxKittyOwned);
+        assert_ok!(KittyModule::set_max_owned_override(Origin::root(), 1, None));
+        assert_ok!(KittyModule::transfer(Origin::signed(2), kitty_id2, 1));
     })
 }
\ No newline at end of file
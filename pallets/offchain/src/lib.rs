@@ -21,6 +21,8 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+
 pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"btc!");
 
 pub mod crypto {
@@ -76,8 +78,12 @@ pub mod pallet {
 		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
 	}
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub (super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	const ONCHAIN_TX_KEY: &[u8] = b"ocw-demo::storage::tx";
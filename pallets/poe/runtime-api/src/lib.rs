@@ -0,0 +1,48 @@
+//! Runtime API definition for pallet poe.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_core::H256;
+use sp_std::vec::Vec;
+
+pub use pallet_poe::{
+	ClaimChange, ClaimRecord, CredentialStatus, EndorsementInfo, EndorsementStatus, HashAlgorithm,
+	HistoryEntry, ProofAnchor, RevocationInfo,
+};
+
+sp_api::decl_runtime_apis! {
+	pub trait PoeApi<AccountId, BlockNumber, Moment> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Moment: Codec,
+	{
+		/// Endorsement status of a co-signed claim, `None` if no such claim exists.
+		fn endorsement_status(claim: Vec<u8>) -> Option<EndorsementInfo<AccountId>>;
+
+		/// Whether `proof` shows that `leaf` belongs to the batch anchored at `root`.
+		fn verify_inclusion(leaf: H256, proof: Vec<H256>, root: H256) -> bool;
+
+		/// A single-owner claim by its raw bytes or digest.
+		fn claim(claim: Vec<u8>) -> Option<ClaimRecord<AccountId, BlockNumber, Moment>>;
+
+		/// Every single-owner claim of `owner`.
+		fn claims_by_owner(owner: AccountId) -> Vec<ClaimRecord<AccountId, BlockNumber, Moment>>;
+
+		/// Where a single-owner claim by its raw bytes or digest was last set, and the storage
+		/// key to prove it by.
+		fn proof_anchor(claim: Vec<u8>) -> Option<ProofAnchor<BlockNumber, Moment>>;
+
+		/// Creation, transfers and revocation of a single-owner claim by its raw bytes or
+		/// digest, oldest first. Empty if the claim never existed.
+		fn claim_history(claim: Vec<u8>) -> Vec<HistoryEntry<AccountId, BlockNumber, Moment>>;
+
+		/// Tombstone of a revoked single-owner claim by its raw bytes or digest, `None` if it
+		/// was never revoked or was created again since.
+		fn revocation(claim: Vec<u8>) -> Option<RevocationInfo<AccountId, BlockNumber, Vec<u8>>>;
+
+		/// Whether the credential with hash `credential` is valid, who issued it and who it is
+		/// about. `None` if it was never issued.
+		fn credential(credential: H256) -> Option<CredentialStatus<AccountId, BlockNumber>>;
+	}
+}
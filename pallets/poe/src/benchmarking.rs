@@ -1,26 +1,111 @@
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{sp_runtime::traits::Bounded, traits::Currency};
 use frame_system::RawOrigin;
 
 use crate::*;
 
+fn funded_account<T: Config>(who: T::AccountId) -> T::AccountId {
+   T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+   who
+}
+
+/// Fills the history of `claim_id`, so that the next change drops its oldest entry.
+fn fill_history<T: Config>(claim_id: ClaimId<T>, owner: &T::AccountId) {
+   <ClaimHistory<T>>::mutate(claim_id, |history| {
+      while (history.len() as u32) < T::MaxHistoryLength::get() {
+         let entry = HistoryEntry {
+            change: ClaimChange::Transferred { from: owner.clone(), to: owner.clone() },
+            block_number: <frame_system::Pallet<T>>::block_number(),
+            moment: <pallet_timestamp::Pallet<T>>::get(),
+         };
+         history.try_push(entry).unwrap();
+      }
+   });
+}
+
+/// Creates `claim` through the pallet in its most expensive shape: held by an organization
+/// of `owner`, with full metadata, expiring at `expires_at` and with a full history.
+fn existing_claim<T: Config>(
+   owner: &T::AccountId,
+   claim: &[u8],
+   expires_at: T::BlockNumber,
+) -> BoundedVec<u8, T::MaxclaimLength> {
+   let org = NextOrgId::<T>::get();
+   Pallet::<T>::create_org(RawOrigin::Signed(owner.clone()).into()).unwrap();
+   let metadata = metadata::<T>(T::MaxMetadataLength::get() * 3);
+   Pallet::<T>::create_org_claim(RawOrigin::Signed(owner.clone()).into(), org, claim.to_vec(), Some(metadata))
+      .unwrap();
+   Pallet::<T>::renew_claim(RawOrigin::Signed(owner.clone()).into(), claim.to_vec(), Some(expires_at)).unwrap();
+   let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.to_vec()).unwrap();
+   fill_history::<T>(ClaimId::Raw(bounded_claim.clone()), owner);
+   bounded_claim
+}
+
+/// Creates a digest claim through the pallet with a full history.
+fn existing_digest_claim<T: Config>(
+   owner: &T::AccountId,
+   algorithm: HashAlgorithm,
+   digest: &[u8],
+) -> ClaimDigest {
+   Pallet::<T>::create_digest_claim(RawOrigin::Signed(owner.clone()).into(), algorithm, digest.to_vec())
+      .unwrap();
+   let claim_digest = Pallet::<T>::claim_digest(algorithm, digest).unwrap();
+   fill_history::<T>(ClaimId::Digest(claim_digest.clone()), owner);
+   claim_digest
+}
+
+/// A delegate of `owner`, so that calls go through the more expensive delegate check.
+fn delegate_of<T: Config>(owner: &T::AccountId) -> T::AccountId {
+   let delegate: T::AccountId = account("delegate", 0, 0);
+   Pallet::<T>::add_delegate(RawOrigin::Signed(owner.clone()).into(), delegate.clone(), None).unwrap();
+   delegate
+}
+
+/// A claim of the longest length, distinct for each `index`.
+fn batch_claim<T: Config>(index: u32) -> Vec<u8> {
+   let mut claim = index.encode();
+   claim.resize(T::MaxclaimLength::get() as usize, 0);
+   claim
+}
+
+/// The block `blocks` after the current one.
+fn later<T: Config>(blocks: u32) -> T::BlockNumber {
+   <frame_system::Pallet<T>>::block_number() + blocks.into()
+}
+
+/// Metadata of `m` bytes in total, spread over the fields.
+fn metadata<T: Config>(m: u32) -> ClaimMetadata<Vec<u8>> {
+   let field = T::MaxMetadataLength::get();
+   let title = m.min(field);
+   let mime_type = (m - title).min(field);
+   let uri = m - title - mime_type;
+   ClaimMetadata {
+      title: vec![0; title as usize],
+      mime_type: vec![0; mime_type as usize],
+      uri: vec![0; uri as usize],
+   }
+}
+
 benchmarks! {
    create_claim {
       let d in 0 .. T::MaxclaimLength::get();
+      let m in 0 .. T::MaxMetadataLength::get() * 3;
       let claim = vec![0; d as usize];
-      let caller: T::AccountId = whitelisted_caller();
-   }: _(RawOrigin::Signed(caller.clone()), claim.clone())
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let metadata = metadata::<T>(m);
+   }: _(RawOrigin::Signed(caller.clone()), claim.clone(), Some(metadata))
    	verify {
         let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim).unwrap();
-		assert_eq!(Proofs::<T>::get(&bounded_claim), Some((caller, <frame_system::Pallet<T>>::block_number())));
+		assert_eq!(Proofs::<T>::get(&bounded_claim).map(|p| p.owner), Some(caller));
 	}
 
    revoke_claim {
       let d in 0 .. T::MaxclaimLength::get();
+      let r in 0 .. T::MaxReasonLength::get();
       let claim = vec![0; d as usize];
-      let caller: T::AccountId = whitelisted_caller();
-      let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone()).unwrap();
-      <Proofs<T>>::insert(&bounded_claim, (&caller, <frame_system::Pallet<T>>::block_number()));
-   }: _(RawOrigin::Signed(caller), claim)
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let bounded_claim = existing_claim::<T>(&caller, &claim, later::<T>(100));
+   }: _(RawOrigin::Signed(caller), claim, vec![0; r as usize])
     verify {
         assert_eq!(Proofs::<T>::contains_key(&bounded_claim), false);
 	}
@@ -28,15 +113,418 @@ benchmarks! {
    transfer_claim {
       let d in 0 .. T::MaxclaimLength::get();
       let claim = vec![0; d as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let dest: T::AccountId = funded_account::<T>(account("dest", 0, 0));
+      let bounded_claim = existing_claim::<T>(&caller, &claim, later::<T>(100));
+      // Runtimes that disable direct transfers re-enable them under `runtime-benchmarks`.
+      assert!(T::AllowDirectTransfer::get(), "direct transfers must be enabled for benchmarking");
+   }: _(RawOrigin::Signed(caller), claim, dest.clone())
+    verify {
+		assert_eq!(Proofs::<T>::get(&bounded_claim).map(|p| p.owner), Some(dest));
+	}
+
+   create_digest_claim {
+      let algorithm = HashAlgorithm::Blake2_256;
+      let digest = algorithm.hash(b"document");
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let claim_digest = Pallet::<T>::claim_digest(algorithm, &digest).unwrap();
+   }: _(RawOrigin::Signed(caller.clone()), algorithm, digest)
+    verify {
+		assert_eq!(DigestProofs::<T>::get(&claim_digest).map(|p| p.owner), Some(caller));
+	}
+
+   revoke_digest_claim {
+      let r in 0 .. T::MaxReasonLength::get();
+      let algorithm = HashAlgorithm::Blake2_256;
+      let digest = algorithm.hash(b"document");
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let claim_digest = existing_digest_claim::<T>(&caller, algorithm, &digest);
+      let delegate = delegate_of::<T>(&caller);
+   }: _(RawOrigin::Signed(delegate), algorithm, digest, vec![0; r as usize])
+    verify {
+        assert_eq!(DigestProofs::<T>::contains_key(&claim_digest), false);
+	}
+
+   transfer_digest_claim {
+      let algorithm = HashAlgorithm::Blake2_256;
+      let digest = algorithm.hash(b"document");
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let dest: T::AccountId = funded_account::<T>(account("dest", 0, 0));
+      let claim_digest = existing_digest_claim::<T>(&caller, algorithm, &digest);
+      let delegate = delegate_of::<T>(&caller);
+      assert!(T::AllowDirectTransfer::get(), "direct transfers must be enabled for benchmarking");
+   }: _(RawOrigin::Signed(delegate), algorithm, digest, dest.clone())
+    verify {
+		assert_eq!(DigestProofs::<T>::get(&claim_digest).map(|p| p.owner), Some(dest));
+	}
+
+   create_claim_with_expiry {
+      let d in 0 .. T::MaxclaimLength::get();
+      let claim = vec![0; d as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let expires_at = later::<T>(10);
+   }: _(RawOrigin::Signed(caller.clone()), claim.clone(), expires_at)
+    verify {
+        let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim).unwrap();
+		assert!(Expiries::<T>::contains_key(expires_at, &bounded_claim));
+	}
+
+   renew_claim {
+      let d in 0 .. T::MaxclaimLength::get();
+      let claim = vec![0; d as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let expires_at = later::<T>(10);
+      existing_claim::<T>(&caller, &claim, expires_at);
+      let renewed = expires_at + 10u32.into();
+   }: _(RawOrigin::Signed(caller), claim.clone(), Some(renewed))
+    verify {
+        let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim).unwrap();
+		assert!(Expiries::<T>::contains_key(renewed, &bounded_claim));
+		assert!(!Expiries::<T>::contains_key(expires_at, &bounded_claim));
+	}
+
+   expire_claim {
+      let d in 0 .. T::MaxclaimLength::get();
+      let claim = vec![0; d as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let expires_at = later::<T>(1);
+      let bounded_claim = existing_claim::<T>(&caller, &claim, expires_at);
+   }: {
+      Pallet::<T>::expire(expires_at, bounded_claim.clone());
+   }
+    verify {
+        assert_eq!(Proofs::<T>::contains_key(&bounded_claim), false);
+	}
+
+   propose_cosigned_claim {
+      let d in 0 .. T::MaxclaimLength::get();
+      let c in 1 .. T::MaxCosigners::get();
+      let claim = vec![0; d as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let cosigners: Vec<T::AccountId> = (0..c).map(|i| account("cosigner", i, 0)).collect();
+   }: _(RawOrigin::Signed(caller), claim.clone(), cosigners, c)
+    verify {
+        let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim).unwrap();
+		assert!(CosignedClaims::<T>::contains_key(&bounded_claim));
+	}
+
+   endorse_claim {
+      let d in 0 .. T::MaxclaimLength::get();
+      let c in 1 .. T::MaxCosigners::get();
+      let claim = vec![0; d as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let cosigners: Vec<T::AccountId> = (0..c).map(|i| account("cosigner", i, 0)).collect();
+      Pallet::<T>::propose_cosigned_claim(RawOrigin::Signed(caller).into(), claim.clone(), cosigners.clone(), c)?;
+      // Leave the last endorsement for the benchmark so that it finalizes the claim.
+      for cosigner in &cosigners[..c as usize - 1] {
+         Pallet::<T>::endorse_claim(RawOrigin::Signed(cosigner.clone()).into(), claim.clone())?;
+      }
+      let endorser = cosigners[c as usize - 1].clone();
+   }: _(RawOrigin::Signed(endorser), claim.clone())
+    verify {
+		assert_eq!(
+			Pallet::<T>::endorsement_status(claim).map(|info| info.status),
+			Some(EndorsementStatus::Finalized)
+		);
+	}
+
+   // The last approval of a finalized claim, which revokes it.
+   revoke_cosigned_claim {
+      let d in 0 .. T::MaxclaimLength::get();
+      let r in 0 .. T::MaxReasonLength::get();
+      let claim = vec![0; d as usize];
+      let proposer: T::AccountId = funded_account::<T>(account("proposer", 0, 0));
       let caller: T::AccountId = whitelisted_caller();
-      let caller2: T::AccountId = whitelisted_caller();
+      Pallet::<T>::propose_cosigned_claim(RawOrigin::Signed(proposer.clone()).into(), claim.clone(), vec![caller.clone()], 1)?;
+      Pallet::<T>::endorse_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
       let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone()).unwrap();
-      <Proofs<T>>::insert(&bounded_claim, (&caller, <frame_system::Pallet<T>>::block_number()));
-   }: _(RawOrigin::Signed(caller), claim, caller2.clone())
+      fill_history::<T>(ClaimId::Raw(bounded_claim.clone()), &proposer);
+   }: _(RawOrigin::Signed(caller), claim, vec![0; r as usize])
+    verify {
+		assert!(!CosignedClaims::<T>::contains_key(&bounded_claim));
+		assert!(Revoked::<T>::contains_key(ClaimId::Raw(bounded_claim)));
+	}
+
+   propose_transfer {
+      let d in 0 .. T::MaxclaimLength::get();
+      let claim = vec![0; d as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let dest: T::AccountId = account("dest", 0, 0);
+      let bounded_claim = existing_claim::<T>(&caller, &claim, later::<T>(100));
+   }: _(RawOrigin::Signed(caller), claim, dest.clone())
+    verify {
+		assert_eq!(PendingTransfers::<T>::get(&bounded_claim), Some(dest));
+	}
+
+   accept_transfer {
+      let d in 0 .. T::MaxclaimLength::get();
+      let claim = vec![0; d as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let dest: T::AccountId = funded_account::<T>(account("dest", 0, 0));
+      let bounded_claim = existing_claim::<T>(&caller, &claim, later::<T>(100));
+      <PendingTransfers<T>>::insert(&bounded_claim, &dest);
+   }: _(RawOrigin::Signed(dest.clone()), claim)
+    verify {
+		assert_eq!(Proofs::<T>::get(&bounded_claim).map(|p| p.owner), Some(dest));
+	}
+
+   cancel_transfer {
+      let d in 0 .. T::MaxclaimLength::get();
+      let claim = vec![0; d as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let dest: T::AccountId = account("dest", 0, 0);
+      let bounded_claim = existing_claim::<T>(&caller, &claim, later::<T>(100));
+      <PendingTransfers<T>>::insert(&bounded_claim, &dest);
+   }: _(RawOrigin::Signed(caller), claim)
+    verify {
+		assert!(!PendingTransfers::<T>::contains_key(&bounded_claim));
+	}
+
+   propose_digest_transfer {
+      let algorithm = HashAlgorithm::Blake2_256;
+      let digest = algorithm.hash(b"document");
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let dest: T::AccountId = account("dest", 0, 0);
+      let claim_digest = existing_digest_claim::<T>(&caller, algorithm, &digest);
+      let delegate = delegate_of::<T>(&caller);
+   }: _(RawOrigin::Signed(delegate), algorithm, digest, dest.clone())
+    verify {
+		assert_eq!(PendingDigestTransfers::<T>::get(&claim_digest), Some(dest));
+	}
+
+   accept_digest_transfer {
+      let algorithm = HashAlgorithm::Blake2_256;
+      let digest = algorithm.hash(b"document");
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let dest: T::AccountId = funded_account::<T>(account("dest", 0, 0));
+      let claim_digest = existing_digest_claim::<T>(&caller, algorithm, &digest);
+      <PendingDigestTransfers<T>>::insert(&claim_digest, &dest);
+   }: _(RawOrigin::Signed(dest.clone()), algorithm, digest)
+    verify {
+		assert_eq!(DigestProofs::<T>::get(&claim_digest).map(|p| p.owner), Some(dest));
+	}
+
+   cancel_digest_transfer {
+      let algorithm = HashAlgorithm::Blake2_256;
+      let digest = algorithm.hash(b"document");
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let dest: T::AccountId = account("dest", 0, 0);
+      let claim_digest = existing_digest_claim::<T>(&caller, algorithm, &digest);
+      <PendingDigestTransfers<T>>::insert(&claim_digest, &dest);
+      let delegate = delegate_of::<T>(&caller);
+   }: _(RawOrigin::Signed(delegate), algorithm, digest)
+    verify {
+		assert!(!PendingDigestTransfers::<T>::contains_key(&claim_digest));
+	}
+
+   anchor_batch {
+      let m in 0 .. T::MaxBatchMetadata::get();
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let root = H256::repeat_byte(1);
+   }: _(RawOrigin::Signed(caller), root, 1000, vec![0; m as usize])
+    verify {
+		assert!(Batches::<T>::contains_key(&root));
+	}
+
+   remove_batch {
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let root = H256::repeat_byte(1);
+      let metadata = vec![0; T::MaxBatchMetadata::get() as usize];
+      Pallet::<T>::anchor_batch(RawOrigin::Signed(caller.clone()).into(), root, 1000, metadata)?;
+   }: _(RawOrigin::Signed(caller), root)
+    verify {
+		assert!(!Batches::<T>::contains_key(&root));
+	}
+
+   purge_claim_history {
+      let claim = vec![0; T::MaxclaimLength::get() as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let bounded_claim = existing_claim::<T>(&caller, &claim, later::<T>(100));
+      Pallet::<T>::revoke_claim(RawOrigin::Signed(caller.clone()).into(), claim, vec![])?;
+      let claim_id = ClaimId::Raw(bounded_claim);
+      let purgeable_at = <frame_system::Pallet<T>>::block_number() + T::TombstoneLifetime::get();
+      <frame_system::Pallet<T>>::set_block_number(purgeable_at);
+   }: _(RawOrigin::Signed(caller), claim_id.clone())
+    verify {
+		assert!(!ClaimHistory::<T>::contains_key(&claim_id));
+		assert!(!RetainedDeposits::<T>::contains_key(&claim_id));
+		assert!(Revoked::<T>::contains_key(&claim_id));
+	}
+
+   set_claim_metadata {
+      let d in 0 .. T::MaxclaimLength::get();
+      let m in 0 .. T::MaxMetadataLength::get() * 3;
+      let claim = vec![0; d as usize];
+      let owner: T::AccountId = funded_account::<T>(account("owner", 0, 0));
+      let bounded_claim = existing_claim::<T>(&owner, &claim, later::<T>(100));
+      Pallet::<T>::set_claim_metadata(RawOrigin::Signed(owner.clone()).into(), claim.clone(), None)?;
+      // Another admin of the organization pays the deposit for the new metadata.
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let (org, _) = OrgClaims::<T>::get(&bounded_claim).unwrap();
+      Pallet::<T>::set_org_member(RawOrigin::Signed(owner).into(), org, caller.clone(), OrgRole::Admin)?;
+      let metadata = metadata::<T>(m);
+   }: _(RawOrigin::Signed(caller), claim, Some(metadata))
+    verify {
+		assert!(ClaimMetadataOf::<T>::contains_key(&bounded_claim));
+	}
+
+   create_org {
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+   }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+		assert_eq!(OrgMembers::<T>::get(0, &caller), Some(OrgRole::Admin));
+	}
+
+   set_org_member {
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let who: T::AccountId = account("member", 0, 0);
+      Pallet::<T>::create_org(RawOrigin::Signed(caller.clone()).into())?;
+   }: _(RawOrigin::Signed(caller), 0, who.clone(), OrgRole::Admin)
+    verify {
+		assert_eq!(Orgs::<T>::get(0).map(|info| info.admins), Some(2));
+	}
+
+   remove_org_member {
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let who: T::AccountId = account("member", 0, 0);
+      Pallet::<T>::create_org(RawOrigin::Signed(caller.clone()).into())?;
+      Pallet::<T>::set_org_member(RawOrigin::Signed(caller.clone()).into(), 0, who.clone(), OrgRole::Admin)?;
+   }: _(RawOrigin::Signed(caller), 0, who.clone())
+    verify {
+		assert!(!OrgMembers::<T>::contains_key(0, &who));
+	}
+
+   create_org_claim {
+      let d in 0 .. T::MaxclaimLength::get();
+      let m in 0 .. T::MaxMetadataLength::get() * 3;
+      let claim = vec![0; d as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      Pallet::<T>::create_org(RawOrigin::Signed(caller.clone()).into())?;
+      let metadata = metadata::<T>(m);
+   }: _(RawOrigin::Signed(caller.clone()), 0, claim.clone(), Some(metadata))
+    verify {
+      let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim).unwrap();
+		assert_eq!(OrgClaims::<T>::get(&bounded_claim), Some((0, caller)));
+		assert_eq!(Proofs::<T>::get(&bounded_claim).map(|p| p.owner), Some(Pallet::<T>::org_account(0)));
+	}
+
+   add_issuer {
+      let issuer: T::AccountId = account("issuer", 0, 0);
+   }: _(RawOrigin::Root, issuer.clone())
+    verify {
+		assert!(TrustedIssuers::<T>::contains_key(&issuer));
+	}
+
+   remove_issuer {
+      let issuer: T::AccountId = account("issuer", 0, 0);
+      <TrustedIssuers<T>>::insert(&issuer, ());
+   }: _(RawOrigin::Root, issuer.clone())
+    verify {
+		assert!(!TrustedIssuers::<T>::contains_key(&issuer));
+	}
+
+   issue_credential {
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let subject: T::AccountId = account("subject", 0, 0);
+      let credential = H256::repeat_byte(1);
+      let expires_at = later::<T>(100);
+      <TrustedIssuers<T>>::insert(&caller, ());
+      let schema = H256::repeat_byte(2);
+   }: _(RawOrigin::Signed(caller.clone()), credential, subject, schema, Some(expires_at))
+    verify {
+		let status = Pallet::<T>::credential_status(credential);
+		assert!(status.map_or(false, |status| status.valid));
+	}
+
+   revoke_credential {
+      let r in 0 .. T::MaxReasonLength::get();
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let subject: T::AccountId = account("subject", 0, 0);
+      let credential = H256::repeat_byte(1);
+      <TrustedIssuers<T>>::insert(&caller, ());
+      Pallet::<T>::issue_credential(
+         RawOrigin::Signed(caller.clone()).into(),
+         credential,
+         subject,
+         H256::repeat_byte(2),
+         None,
+      )?;
+      let delegate = delegate_of::<T>(&caller);
+   }: _(RawOrigin::Signed(delegate), credential, vec![0; r as usize])
+    verify {
+		let status = Pallet::<T>::credential_status(credential);
+		assert!(status.map_or(false, |status| !status.valid));
+	}
+
+   create_indexed_claim {
+      let p in 0 .. T::MaxPayloadLength::get();
+      let algorithm = HashAlgorithm::Blake2_256;
+      let payload = vec![0; p as usize];
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let claim_digest = Pallet::<T>::claim_digest(algorithm, &algorithm.hash(&payload)).unwrap();
+   }: _(RawOrigin::Signed(caller.clone()), algorithm, payload)
+    verify {
+		assert_eq!(DigestProofs::<T>::get(&claim_digest).map(|p| p.owner), Some(caller));
+	}
+
+   // Best effort batches also open a transaction and emit an event per claim, so their weight
+   // is an upper bound for atomic ones.
+   create_claims {
+      let n in 1 .. T::MaxBatchSize::get();
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let claims: Vec<Vec<u8>> = (0..n).map(batch_claim::<T>).collect();
+   }: _(RawOrigin::Signed(caller.clone()), claims.clone(), BatchMode::BestEffort)
+    verify {
+		for claim in claims {
+			assert_eq!(Pallet::<T>::claim_record(claim).map(|record| record.owner), Some(caller.clone()));
+		}
+	}
+
+   revoke_claims {
+      let n in 1 .. T::MaxBatchSize::get();
+      let r in 0 .. T::MaxReasonLength::get();
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let claims: Vec<Vec<u8>> = (0..n).map(batch_claim::<T>).collect();
+      for claim in &claims {
+         existing_claim::<T>(&caller, claim, later::<T>(100));
+      }
+   }: _(RawOrigin::Signed(caller), claims.clone(), vec![0; r as usize], BatchMode::BestEffort)
+    verify {
+		for claim in claims {
+			assert!(Pallet::<T>::claim_record(claim).is_none());
+		}
+	}
+
+   add_delegate {
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let delegate: T::AccountId = account("delegate", 0, 0);
+      let expires_at = later::<T>(100);
+   }: _(RawOrigin::Signed(caller.clone()), delegate.clone(), Some(expires_at))
+    verify {
+		assert!(Pallet::<T>::is_delegate(&caller, &delegate));
+	}
+
+   remove_delegate {
+      let caller: T::AccountId = funded_account::<T>(whitelisted_caller());
+      let delegate = delegate_of::<T>(&caller);
+   }: _(RawOrigin::Signed(caller.clone()), delegate.clone())
+    verify {
+		assert!(!Delegates::<T>::contains_key(&caller, &delegate));
+	}
+
+   remove_expired_delegate {
+      let caller: T::AccountId = whitelisted_caller();
+      let owner: T::AccountId = funded_account::<T>(account("owner", 0, 0));
+      let delegate: T::AccountId = account("delegate", 0, 0);
+      let expires_at = later::<T>(1);
+      Pallet::<T>::add_delegate(RawOrigin::Signed(owner.clone()).into(), delegate.clone(), Some(expires_at))
+         .unwrap();
+      <frame_system::Pallet<T>>::set_block_number(expires_at);
+   }: _(RawOrigin::Signed(caller), owner.clone(), delegate.clone())
     verify {
-		assert_eq!(Proofs::<T>::get(&bounded_claim), Some((caller2, <frame_system::Pallet<T>>::block_number())));
+		assert!(!Delegates::<T>::contains_key(&owner, &delegate));
 	}
 
    	impl_benchmark_test_suite!(PoeModule, crate::mock::new_test_ext(), crate::mock::Test);
 
-}
\ No newline at end of file
+}
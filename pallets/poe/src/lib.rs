@@ -6,6 +6,9 @@
 pub use pallet::*;
 pub use weights::WeightInfo;
 
+pub mod merkle;
+pub mod migrations;
+pub mod payload;
 pub mod weights;
 
 #[cfg(test)]
@@ -22,23 +25,412 @@ pub mod pallet {
 	pub use frame_support::{
 		Blake2_128Concat,
 		BoundedVec, pallet_prelude::{*, DispatchResultWithPostInfo},
+		traits::{BalanceStatus, Currency, ReservableCurrency},
+		PalletId,
+	};
+	use frame_support::{
+		sp_runtime::{
+			traits::{AccountIdConversion, One, Saturating, Zero},
+			TransactionOutcome,
+		},
+		storage::with_transaction,
+		weights::WithPostDispatchInfo,
 	};
 	pub use frame_system::pallet_prelude::*;
+	pub use sp_core::H256;
 	pub use sp_std::prelude::*;
+	#[cfg(feature = "std")]
+	use serde::{Deserialize, Serialize};
 
 	use super::WeightInfo;
 
+	/// The longest digest produced by any [`HashAlgorithm`].
+	pub type MaxDigestLength = ConstU32<64>;
+
+	/// Hash algorithms accepted for digest claims.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum HashAlgorithm {
+		Blake2_256,
+		Sha2_256,
+		Keccak256,
+	}
+
+	impl HashAlgorithm {
+		/// Length in bytes of the digests this algorithm produces.
+		pub fn digest_len(&self) -> u32 {
+			match self {
+				HashAlgorithm::Blake2_256 | HashAlgorithm::Sha2_256 | HashAlgorithm::Keccak256 => 32,
+			}
+		}
+
+		/// Hashes `data` with this algorithm.
+		pub fn hash(&self, data: &[u8]) -> Vec<u8> {
+			match self {
+				HashAlgorithm::Blake2_256 => sp_io::hashing::blake2_256(data).to_vec(),
+				HashAlgorithm::Sha2_256 => sp_io::hashing::sha2_256(data).to_vec(),
+				HashAlgorithm::Keccak256 => sp_io::hashing::keccak_256(data).to_vec(),
+			}
+		}
+	}
+
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
+	/// What is recorded about a claim.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct ProofInfo<AccountId, BlockNumber, Moment, Balance> {
+		pub owner: AccountId,
+		pub block_number: BlockNumber,
+		/// Timestamp of `block_number`. Zero for claims made before timestamps were recorded.
+		pub moment: Moment,
+		/// Index of the extrinsic within `block_number` that last set the claim.
+		pub extrinsic_index: Option<u32>,
+		/// Amount reserved from `owner` for as long as the claim exists.
+		pub deposit: Balance,
+		/// Block from which the claim no longer holds. Only raw claims can expire.
+		pub expires_at: Option<BlockNumber>,
+	}
+
+	pub type ProofInfoOf<T> = ProofInfo<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		<T as pallet_timestamp::Config>::Moment,
+		BalanceOf<T>,
+	>;
+
+	/// A claim on a document identified by its digest instead of its bytes.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct ClaimDigest {
+		pub algorithm: HashAlgorithm,
+		pub digest: BoundedVec<u8, MaxDigestLength>,
+	}
+
+	/// Progress of a co-signed claim.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum EndorsementStatus {
+		/// Waiting for more co-signers to endorse.
+		Pending,
+		/// Enough co-signers have endorsed the claim.
+		Finalized,
+	}
+
+	/// A claim attested to by several accounts.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct CosignedClaim<T: Config> {
+		pub proposer: T::AccountId,
+		pub block_number: T::BlockNumber,
+		/// Amount reserved from `proposer` for as long as the claim exists.
+		pub deposit: BalanceOf<T>,
+		/// Accounts asked to endorse the claim.
+		pub cosigners: BoundedVec<T::AccountId, T::MaxCosigners>,
+		/// Co-signers that have endorsed so far.
+		pub endorsed: BoundedVec<T::AccountId, T::MaxCosigners>,
+		/// Number of endorsements needed to finalize the claim.
+		pub threshold: u32,
+		pub status: EndorsementStatus,
+	}
+
+	/// Endorsement status of a co-signed claim, as returned by the runtime API.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct EndorsementInfo<AccountId> {
+		pub status: EndorsementStatus,
+		pub threshold: u32,
+		pub endorsed: Vec<AccountId>,
+		pub pending: Vec<AccountId>,
+	}
+
+	/// Descriptive metadata of a raw claim.
+	#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct ClaimMetadata<Bytes> {
+		pub title: Bytes,
+		/// MIME type of the document, e.g. `application/pdf`.
+		pub mime_type: Bytes,
+		/// Where the document itself can be found.
+		pub uri: Bytes,
+	}
+
+	impl ClaimMetadata<Vec<u8>> {
+		/// Number of bytes the deposit is charged for.
+		pub fn len(&self) -> usize {
+			self.title.len() + self.mime_type.len() + self.uri.len()
+		}
+	}
+
+	pub type BoundedClaimMetadata<T> = ClaimMetadata<BoundedVec<u8, <T as Config>::MaxMetadataLength>>;
+
+	/// Key of a single-owner claim in either `Proofs` or `DigestProofs`.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub enum ClaimId<T: Config> {
+		Raw(BoundedVec<u8, T::MaxclaimLength>),
+		Digest(ClaimDigest),
+	}
+
+	/// How `create_claims` and `revoke_claims` treat failing claims.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum BatchMode {
+		/// Any failing claim fails the whole call.
+		Atomic,
+		/// Each claim is processed on its own and its result reported in a `ClaimBatchItem`
+		/// event.
+		BestEffort,
+	}
+
+	/// A single-owner claim set at genesis, by its raw bytes or by the digest of a document.
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum GenesisClaim {
+		Raw(#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))] Vec<u8>),
+		Digest(
+			HashAlgorithm,
+			#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))] Vec<u8>,
+		),
+	}
+
+	/// A single-owner claim, as returned by the runtime API.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct ClaimRecord<AccountId, BlockNumber, Moment> {
+		/// The raw claim bytes, or the digest of a digest claim.
+		pub claim: Vec<u8>,
+		/// The algorithm of a digest claim, `None` for a raw claim.
+		pub algorithm: Option<HashAlgorithm>,
+		pub owner: AccountId,
+		pub block_number: BlockNumber,
+		/// Timestamp of `block_number`. Zero for claims made before timestamps were recorded.
+		pub moment: Moment,
+		pub expires_at: Option<BlockNumber>,
+		/// The organization holding the claim, if any. `owner` is then its account.
+		pub org: Option<OrgId>,
+		/// The member that submitted a claim of an organization.
+		pub submitter: Option<AccountId>,
+	}
+
+	pub type ClaimRecordOf<T> = ClaimRecord<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		<T as pallet_timestamp::Config>::Moment,
+	>;
+
+	/// Where a single-owner claim was last set, as returned by the runtime API.
+	///
+	/// A node turns this into a certificate by adding the hash of `block_number` and a proof
+	/// of `key` against the state root of a finalized block.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct ProofAnchor<BlockNumber, Moment> {
+		/// Storage key of the claim in `Proofs` or `DigestProofs`.
+		pub key: Vec<u8>,
+		/// Block the claim was created or last transferred in.
+		pub block_number: BlockNumber,
+		/// Timestamp of `block_number`. Zero for claims made before timestamps were recorded.
+		pub moment: Moment,
+		/// Index of the extrinsic within `block_number` that set the claim, if known.
+		pub extrinsic_index: Option<u32>,
+	}
+
+	pub type ProofAnchorOf<T> = ProofAnchor<
+		<T as frame_system::Config>::BlockNumber,
+		<T as pallet_timestamp::Config>::Moment,
+	>;
+
+	/// Tombstone of a revoked single-owner claim.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct RevocationInfo<AccountId, BlockNumber, Reason> {
+		/// Owner of the claim when it was revoked.
+		pub owner: AccountId,
+		/// Account that signed the revocation: the owner, one of its delegates or an admin of its
+		/// organization.
+		pub revoked_by: AccountId,
+		pub block_number: BlockNumber,
+		pub reason: Reason,
+	}
+
+	pub type RevocationInfoOf<T> = RevocationInfo<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		BoundedVec<u8, <T as Config>::MaxReasonLength>,
+	>;
+
+	/// A credential anchored by a trusted issuer.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct CredentialInfo<AccountId, BlockNumber> {
+		pub issuer: AccountId,
+		/// Account the credential is about.
+		pub subject: AccountId,
+		/// Hash of the schema the credential follows.
+		pub schema: H256,
+		pub issued_at: BlockNumber,
+	}
+
+	pub type CredentialInfoOf<T> = CredentialInfo<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// Status of a credential, as returned by the runtime API.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct CredentialStatus<AccountId, BlockNumber> {
+		pub issuer: AccountId,
+		pub subject: AccountId,
+		pub schema: H256,
+		pub issued_at: BlockNumber,
+		pub expires_at: Option<BlockNumber>,
+		/// Set once the issuer or the subject revoked the credential.
+		pub revocation: Option<RevocationInfo<AccountId, BlockNumber, Vec<u8>>>,
+		/// Whether the issuer is still trusted and still holds the unexpired, unrevoked
+		/// credential claim.
+		pub valid: bool,
+	}
+
+	pub type OrgId = u32;
+
+	/// Role of an account in an organization.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum OrgRole {
+		/// Manages members and the claims of the organization.
+		Admin,
+		/// Creates claims on behalf of the organization.
+		Member,
+	}
+
+	/// An organization holding claims on behalf of its members.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct OrgInfo<AccountId, Balance> {
+		pub creator: AccountId,
+		/// Number of admins, never zero.
+		pub admins: u32,
+		/// Number of members, admins included.
+		pub members: u32,
+		/// Amount reserved from `creator` for as long as the organization exists.
+		pub deposit: Balance,
+	}
+
+	pub type OrgInfoOf<T> = OrgInfo<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+	/// An account allowed to revoke and transfer the claims of an owner.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct DelegateInfo<BlockNumber, Balance> {
+		/// The block the delegation ends at, if any.
+		pub expires_at: Option<BlockNumber>,
+		/// Reserved from the owner until the delegation is removed.
+		pub deposit: Balance,
+	}
+
+	pub type DelegateInfoOf<T> =
+		DelegateInfo<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+
+	/// A change to a single-owner or finalized co-signed claim, as kept in its history.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum ClaimChange<AccountId> {
+		Created { owner: AccountId },
+		Transferred { from: AccountId, to: AccountId },
+		Revoked { owner: AccountId },
+		Expired { owner: AccountId },
+	}
+
+	/// An entry in the history of a claim.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct HistoryEntry<AccountId, BlockNumber, Moment> {
+		pub change: ClaimChange<AccountId>,
+		pub block_number: BlockNumber,
+		/// Timestamp of `block_number`.
+		pub moment: Moment,
+	}
+
+	pub type HistoryEntryOf<T> = HistoryEntry<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		<T as pallet_timestamp::Config>::Moment,
+	>;
+
+	/// A batch of documents anchored by the root of their Merkle tree.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct BatchInfo<T: Config> {
+		pub owner: T::AccountId,
+		pub block_number: T::BlockNumber,
+		pub leaf_count: u32,
+		pub metadata: BoundedVec<u8, T::MaxBatchMetadata>,
+		/// Amount reserved from `owner` for as long as the batch is anchored.
+		pub deposit: BalanceOf<T>,
+	}
+
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: frame_system::Config + pallet_timestamp::Config {
 		/// The maximum length of claim that can be added.
 		#[pallet::constant]
 		type MaxclaimLength: Get<u32>;
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type WeightInfo: WeightInfo;
+		/// The currency claim deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The deposit reserved per byte of claim.
+		#[pallet::constant]
+		type ClaimDeposit: Get<BalanceOf<Self>>;
+		/// The maximum number of co-signers of a claim.
+		#[pallet::constant]
+		type MaxCosigners: Get<u32>;
+		/// Whether `transfer_claim` and `transfer_digest_claim` may move a claim without the
+		/// recipient's consent. Deprecated in favour of `propose_transfer` and `accept_transfer`,
+		/// or their digest counterparts.
+		#[pallet::constant]
+		type AllowDirectTransfer: Get<bool>;
+		/// The maximum length of the metadata of an anchored batch.
+		#[pallet::constant]
+		type MaxBatchMetadata: Get<u32>;
+		/// The maximum length of each metadata field of a claim.
+		#[pallet::constant]
+		type MaxMetadataLength: Get<u32>;
+		/// The maximum number of entries in the history of a claim.
+		#[pallet::constant]
+		type MaxHistoryLength: Get<u32>;
+		/// The maximum length of the reason given for a revocation.
+		#[pallet::constant]
+		type MaxReasonLength: Get<u32>;
+		/// Whether a revoked claim may be created again, which clears its tombstone.
+		#[pallet::constant]
+		type AllowRecreateRevoked: Get<bool>;
+		/// The maximum length of a document passed to `create_indexed_claim`.
+		#[pallet::constant]
+		type MaxPayloadLength: Get<u32>;
+		/// The maximum number of claims passed to `create_claims` or `revoke_claims`.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+		/// The deposit reserved from an owner for each of its delegates.
+		#[pallet::constant]
+		type DelegateDeposit: Get<BalanceOf<Self>>;
+		/// The deposit reserved with each claim for its history of up to `MaxHistoryLength`
+		/// entries.
+		#[pallet::constant]
+		type HistoryDeposit: Get<BalanceOf<Self>>;
+		/// The deposit reserved from the creator of an organization.
+		#[pallet::constant]
+		type OrgDeposit: Get<BalanceOf<Self>>;
+		/// The deposit reserved from the admin adding a member to an organization.
+		#[pallet::constant]
+		type OrgMemberDeposit: Get<BalanceOf<Self>>;
+		/// The maximum number of members of an organization, admins included.
+		#[pallet::constant]
+		type MaxOrgMembers: Get<u32>;
+		/// The id from which the accounts owning the claims of organizations are derived.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// The number of blocks the history of a revoked or expired claim is kept before anyone
+		/// can purge it. Tombstones of revoked claims are never purged.
+		#[pallet::constant]
+		type TombstoneLifetime: Get<Self::BlockNumber>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -46,15 +438,260 @@ pub mod pallet {
 		_,
 		Blake2_128Concat,
 		BoundedVec<u8, T::MaxclaimLength>,
-		(T::AccountId, T::BlockNumber),
+		ProofInfoOf<T>,
+	>;
+
+	#[pallet::storage]
+	pub type DigestProofs<T: Config> =
+		StorageMap<_, Blake2_128Concat, ClaimDigest, ProofInfoOf<T>>;
+
+	/// Claims by the block they expire at, purged in `on_idle`.
+	#[pallet::storage]
+	pub type Expiries<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Blake2_128Concat,
+		BoundedVec<u8, T::MaxclaimLength>,
+		(),
+	>;
+
+	/// The earliest block whose expiries may not have been purged yet.
+	#[pallet::storage]
+	pub type ExpiryCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// Metadata of raw claims.
+	#[pallet::storage]
+	pub type ClaimMetadataOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxclaimLength>, BoundedClaimMetadata<T>>;
+
+	/// Single-owner claims by owner.
+	#[pallet::storage]
+	pub type ClaimsByOwner<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, ClaimId<T>, ()>;
+
+	/// Latest changes of single-owner claims, kept after they are revoked or expire.
+	#[pallet::storage]
+	pub type ClaimHistory<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ClaimId<T>,
+		BoundedVec<HistoryEntryOf<T>, T::MaxHistoryLength>,
+		ValueQuery,
+	>;
+
+	/// Tombstones of revoked single-owner claims, so that verifiers can tell a revoked claim
+	/// from one that never existed.
+	#[pallet::storage]
+	pub type Revoked<T: Config> = StorageMap<_, Blake2_128Concat, ClaimId<T>, RevocationInfoOf<T>>;
+
+	/// Part of the deposit of a revoked or expired claim kept reserved from its last owner for
+	/// as long as its history and tombstone stay.
+	#[pallet::storage]
+	pub type RetainedDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, ClaimId<T>, (T::AccountId, BalanceOf<T>)>;
+
+	/// Accounts trusted by root to issue credentials.
+	#[pallet::storage]
+	pub type TrustedIssuers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Credentials by the claim anchoring their hash. Kept after revocation so that the
+	/// subject of a revoked credential can still be looked up.
+	#[pallet::storage]
+	pub type Credentials<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxclaimLength>, CredentialInfoOf<T>>;
+
+	/// Organizations by id.
+	#[pallet::storage]
+	pub type Orgs<T: Config> = StorageMap<_, Twox64Concat, OrgId, OrgInfoOf<T>>;
+
+	/// The id of the next organization.
+	#[pallet::storage]
+	pub type NextOrgId<T: Config> = StorageValue<_, OrgId, ValueQuery>;
+
+	/// Roles of the members of each organization.
+	#[pallet::storage]
+	pub type OrgMembers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, OrgId, Blake2_128Concat, T::AccountId, OrgRole>;
+
+	/// Deposits of the members added to each organization, with the admin they are reserved
+	/// from.
+	#[pallet::storage]
+	pub type OrgMemberDeposits<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		OrgId,
+		Blake2_128Concat,
+		T::AccountId,
+		(T::AccountId, BalanceOf<T>),
+	>;
+
+	/// Raw claims owned by the account of an organization, with the member that submitted
+	/// them. Only admins of the organization manage them, while the deposit stays with the
+	/// member.
+	#[pallet::storage]
+	pub type OrgClaims<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BoundedVec<u8, T::MaxclaimLength>,
+		(OrgId, T::AccountId),
+	>;
+
+	/// Anchored batches by Merkle root.
+	#[pallet::storage]
+	pub type Batches<T: Config> = StorageMap<_, Blake2_128Concat, H256, BatchInfo<T>>;
+
+	/// Recipients of proposed claim transfers.
+	#[pallet::storage]
+	pub type PendingTransfers<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxclaimLength>, T::AccountId>;
+
+	/// Recipients of proposed digest claim transfers.
+	#[pallet::storage]
+	pub type PendingDigestTransfers<T: Config> =
+		StorageMap<_, Blake2_128Concat, ClaimDigest, T::AccountId>;
+
+	#[pallet::storage]
+	pub type CosignedClaims<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxclaimLength>, CosignedClaim<T>>;
+
+	/// Co-signers that approved revoking a finalized co-signed claim so far.
+	#[pallet::storage]
+	pub type CosignedRevocations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BoundedVec<u8, T::MaxclaimLength>,
+		BoundedVec<T::AccountId, T::MaxCosigners>,
+		ValueQuery,
+	>;
+
+	/// Accounts allowed to revoke and transfer the claims of an owner.
+	#[pallet::storage]
+	pub type Delegates<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		DelegateInfoOf<T>,
 	>;
 
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Claims existing from genesis, with their owner and the block they were originally
+		/// made in. Owners must be able to afford the deposit.
+		pub claims: Vec<(GenesisClaim, T::AccountId, T::BlockNumber)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { claims: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (claim, owner, block_number) in &self.claims {
+				let (claim_id, len) = match claim {
+					GenesisClaim::Raw(claim) => {
+						let bounded_claim =
+							<BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
+								.expect("genesis claim is too long");
+						assert!(
+							!<Proofs<T>>::contains_key(&bounded_claim),
+							"duplicate genesis claim"
+						);
+						(ClaimId::<T>::Raw(bounded_claim), claim.len())
+					},
+					GenesisClaim::Digest(algorithm, digest) => {
+						let claim_digest = Pallet::<T>::claim_digest(*algorithm, digest)
+							.expect("genesis digest does not match its algorithm");
+						assert!(
+							!<DigestProofs<T>>::contains_key(&claim_digest),
+							"duplicate genesis claim"
+						);
+						(ClaimId::Digest(claim_digest), digest.len())
+					},
+				};
+
+				let deposit = Pallet::<T>::claim_deposit_for(len);
+				T::Currency::reserve(owner, deposit)
+					.expect("genesis claim owner cannot afford the deposit");
+				let proof = ProofInfo {
+					owner: owner.clone(),
+					block_number: *block_number,
+					moment: Zero::zero(),
+					extrinsic_index: None,
+					deposit,
+					expires_at: None,
+				};
+				match &claim_id {
+					ClaimId::Raw(bounded_claim) => <Proofs<T>>::insert(bounded_claim, proof),
+					ClaimId::Digest(claim_digest) => <DigestProofs<T>>::insert(claim_digest, proof),
+				}
+
+				let created = HistoryEntry {
+					change: ClaimChange::Created { owner: owner.clone() },
+					block_number: *block_number,
+					moment: Zero::zero(),
+				};
+				let history = <BoundedVec<_, T::MaxHistoryLength>>::try_from(vec![created])
+					.expect("MaxHistoryLength is at least 1");
+				<ClaimHistory<T>>::insert(&claim_id, history);
+				<ClaimsByOwner<T>>::insert(owner, claim_id, ());
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		ClaimCreated(T::AccountId, Vec<u8>),
-		ClaimRevoked(T::AccountId, Vec<u8>),
+		ClaimCreated(T::AccountId, Vec<u8>, Option<ClaimMetadata<Vec<u8>>>),
+		/// Owner, claim and reason.
+		ClaimRevoked(T::AccountId, Vec<u8>, Vec<u8>),
 		ClaimTransfer(T::AccountId, Vec<u8>, T::AccountId),
+		DigestClaimCreated(T::AccountId, HashAlgorithm, Vec<u8>),
+		DigestClaimRevoked(T::AccountId, HashAlgorithm, Vec<u8>, Vec<u8>),
+		DigestClaimTransfer(T::AccountId, HashAlgorithm, Vec<u8>, T::AccountId),
+		ClaimRenewed(T::AccountId, Vec<u8>, Option<T::BlockNumber>),
+		ClaimExpired(T::AccountId, Vec<u8>),
+		CosignedClaimProposed(T::AccountId, Vec<u8>, u32),
+		ClaimEndorsed(T::AccountId, Vec<u8>),
+		ClaimFinalized(Vec<u8>),
+		CosignedClaimRevoked(T::AccountId, Vec<u8>),
+		TransferProposed(T::AccountId, Vec<u8>, T::AccountId),
+		TransferCancelled(T::AccountId, Vec<u8>),
+		DigestTransferProposed(T::AccountId, HashAlgorithm, Vec<u8>, T::AccountId),
+		DigestTransferCancelled(T::AccountId, HashAlgorithm, Vec<u8>),
+		BatchAnchored(T::AccountId, H256, u32),
+		ClaimMetadataSet(T::AccountId, Vec<u8>, Option<ClaimMetadata<Vec<u8>>>),
+		OrgCreated(OrgId, T::AccountId),
+		OrgMemberSet(OrgId, T::AccountId, OrgRole),
+		OrgMemberRemoved(OrgId, T::AccountId),
+		/// A member created a claim on behalf of an organization.
+		OrgClaimCreated(OrgId, T::AccountId, Vec<u8>),
+		IssuerAdded(T::AccountId),
+		IssuerRemoved(T::AccountId),
+		/// Issuer, credential hash, subject and schema.
+		CredentialIssued(T::AccountId, H256, T::AccountId, H256),
+		/// Revoking account, issuer, credential hash and reason.
+		CredentialRevoked(T::AccountId, T::AccountId, H256, Vec<u8>),
+		/// Signer, index and result of a claim of a best effort `create_claims` or
+		/// `revoke_claims` call.
+		ClaimBatchItem(T::AccountId, u32, DispatchResult),
+		/// Owner, delegate and the block the delegation ends at.
+		DelegateAdded(T::AccountId, T::AccountId, Option<T::BlockNumber>),
+		DelegateRemoved(T::AccountId, T::AccountId),
+		/// Delegate, owner it acted for and claim, or digest of a digest claim. Follows the
+		/// event of the action itself.
+		DelegateActed(T::AccountId, T::AccountId, Vec<u8>),
+		BatchRemoved(T::AccountId, H256),
+		/// History of a revoked or expired claim was removed.
+		ClaimHistoryPurged(ClaimId<T>),
+		/// A co-signer approved revoking a finalized co-signed claim.
+		CosignedRevocationApproved(T::AccountId, Vec<u8>),
 	}
 
 	#[pallet::error]
@@ -63,45 +700,207 @@ pub mod pallet {
 		ClaimTooLong,
 		ClaimNotExist,
 		NotClaimOwner,
+		InvalidDigestLength,
+		/// The expiry is not in the future.
+		InvalidExpiry,
+		ClaimExpired,
+		TooManyCosigners,
+		DuplicateCosigner,
+		/// The threshold is zero or above the number of co-signers.
+		InvalidThreshold,
+		NotCosigner,
+		AlreadyEndorsed,
+		/// Direct transfers are disabled, use `propose_transfer`.
+		DirectTransferDisabled,
+		NoPendingTransfer,
+		NotTransferRecipient,
+		EmptyBatch,
+		BatchAlreadyAnchored,
+		MetadataTooLong,
+		OrgNotExist,
+		NotOrgAdmin,
+		NotOrgMember,
+		/// An organization must keep at least one admin.
+		LastOrgAdmin,
+		ReasonTooLong,
+		/// The claim was revoked and the runtime does not allow creating it again.
+		ClaimWasRevoked,
+		AlreadyTrustedIssuer,
+		NotTrustedIssuer,
+		CredentialNotExist,
+		/// Only the issuer, as for `revoke_claim`, or the subject can revoke a credential.
+		NotCredentialParty,
+		PayloadTooLong,
+		/// More claims than `MaxBatchSize` in one call.
+		BatchTooLarge,
+		DelegateNotExist,
+		/// An account cannot be its own delegate.
+		SelfDelegation,
+		/// Only expired delegations can be removed by others than the owner.
+		DelegateNotExpired,
+		BatchNotExist,
+		/// A claim anchoring a credential stays with its issuer.
+		CredentialNotTransferable,
+		/// The organization already has `MaxOrgMembers` members.
+		TooManyOrgMembers,
+		/// The claim is live or has no history left.
+		ClaimNotEnded,
+		/// The claim ended less than `TombstoneLifetime` blocks ago.
+		TombstoneTooRecent,
+		/// The co-signer already approved revoking the claim.
+		RevocationAlreadyApproved,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let per_step = T::DbWeight::get().reads(1);
+			let per_claim = T::WeightInfo::expire_claim(T::MaxclaimLength::get());
+			let mut used = T::DbWeight::get().reads_writes(1, 1);
+			if used > remaining_weight {
+				return 0
+			}
+
+			let mut cursor = <ExpiryCursor<T>>::get();
+			while cursor <= now && used.saturating_add(per_step) <= remaining_weight {
+				used = used.saturating_add(per_step);
+				match <Expiries<T>>::iter_key_prefix(cursor).next() {
+					Some(bounded_claim) => {
+						if used.saturating_add(per_claim) > remaining_weight {
+							break
+						}
+						used = used.saturating_add(per_claim);
+						Self::expire(cursor, bounded_claim);
+					},
+					None => cursor = cursor.saturating_add(One::one()),
+				}
+			}
+			<ExpiryCursor<T>>::put(cursor);
+
+			used
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(T::WeightInfo::create_claim(claim.len() as u32))]
-		pub fn create_claim(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResultWithPostInfo {
+		#[pallet::weight(T::WeightInfo::create_claim(
+			claim.len() as u32,
+			metadata.as_ref().map_or(0, |metadata| metadata.len() as u32),
+		))]
+		pub fn create_claim(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			metadata: Option<ClaimMetadata<Vec<u8>>>,
+		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
-				.map_err(|_| <Error<T>>::ClaimTooLong)?;
+			Self::do_create_claim(sender.clone(), &sender, claim, None, metadata)?;
 
-			ensure!(!<Proofs<T>>::contains_key(&bounded_claim), <Error<T>>::ProofAlreadyExist);
+			Ok(().into())
+		}
 
-			<Proofs<T>>::insert(
-				&bounded_claim,
-				(&sender, <frame_system::Pallet<T>>::block_number()),
+		/// Replaces or, with `None`, removes the metadata of a claim. The deposit follows the
+		/// new metadata size: the caller pays any increase, which then stays with the claim.
+		#[pallet::weight(T::WeightInfo::set_claim_metadata(
+			claim.len() as u32,
+			metadata.as_ref().map_or(0, |metadata| metadata.len() as u32),
+		))]
+		pub fn set_claim_metadata(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			metadata: Option<ClaimMetadata<Vec<u8>>>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let (bounded_claim, mut proof) = Self::check(&sender, &claim)?;
+			ensure!(!Self::is_expired(&proof), <Error<T>>::ClaimExpired);
+			let bounded_metadata = metadata.clone().map(Self::bound_metadata).transpose()?;
+
+			let deposit = Self::claim_deposit_for(
+				claim.len() + metadata.as_ref().map_or(0, |metadata| metadata.len()),
 			);
+			let depositor = Self::depositor(&bounded_claim, &proof);
+			if deposit > proof.deposit {
+				let increase = deposit - proof.deposit;
+				T::Currency::reserve(&sender, increase)?;
+				Self::move_deposit(&sender, &depositor, increase)?;
+			} else {
+				T::Currency::unreserve(&depositor, proof.deposit - deposit);
+			}
+			proof.deposit = deposit;
 
-			Self::deposit_event(Event::ClaimCreated(sender, claim));
+			<Proofs<T>>::insert(&bounded_claim, proof);
+			<ClaimMetadataOf<T>>::set(&bounded_claim, bounded_metadata);
+
+			Self::deposit_event(Event::ClaimMetadataSet(sender, claim, metadata));
 
 			Ok(().into())
 		}
 
-		#[pallet::weight(T::WeightInfo::revoke_claim(claim.len() as u32))]
-		pub fn revoke_claim(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResultWithPostInfo {
+		/// Creates a claim that stops holding at block `expires_at`.
+		#[pallet::weight(T::WeightInfo::create_claim_with_expiry(claim.len() as u32))]
+		pub fn create_claim_with_expiry(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			expires_at: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let bounded_claim = Self::check(&sender, &claim)?;
+			Self::do_create_claim(sender.clone(), &sender, claim, Some(expires_at), None)?;
 
-			<Proofs<T>>::remove(&bounded_claim);
+			Ok(().into())
+		}
+
+		/// Moves the expiry of a live claim. `None` makes the claim permanent.
+		#[pallet::weight(T::WeightInfo::renew_claim(claim.len() as u32))]
+		pub fn renew_claim(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
 
-			Self::deposit_event(Event::ClaimRevoked(sender, claim));
+			let (bounded_claim, mut proof) = Self::check(&sender, &claim)?;
+			ensure!(!Self::is_expired(&proof), <Error<T>>::ClaimExpired);
+			Self::ensure_valid_expiry(expires_at)?;
+
+			if let Some(old) = proof.expires_at {
+				<Expiries<T>>::remove(old, &bounded_claim);
+			}
+			if let Some(new) = expires_at {
+				<Expiries<T>>::insert(new, &bounded_claim, ());
+			}
+			proof.expires_at = expires_at;
+			<Proofs<T>>::insert(&bounded_claim, proof);
+
+			Self::deposit_event(Event::ClaimRenewed(sender, claim, expires_at));
+
+			Ok(().into())
+		}
+
+		/// Revokes a claim, leaving a tombstone with `reason` behind.
+		#[pallet::weight(T::WeightInfo::revoke_claim(claim.len() as u32, reason.len() as u32))]
+		pub fn revoke_claim(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			reason: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let (bounded_claim, proof, delegated) = Self::check_or_delegate(&sender, &claim)?;
+			let owner = proof.owner.clone();
+
+			Self::do_revoke(bounded_claim, proof, sender.clone(), reason)?;
+
+			if delegated {
+				Self::deposit_event(Event::DelegateActed(sender, owner, claim));
+			}
 
 			Ok(().into())
 		}
 
+		/// Moves a claim to `dest` without its consent. Only available while
+		/// `AllowDirectTransfer` is set.
 		#[pallet::weight(T::WeightInfo::transfer_claim(claim.len() as u32))]
 		pub fn transfer_claim(
 			origin: OriginFor<T>,
@@ -110,29 +909,1491 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let bounded_claim = Self::check(&sender, &claim)?;
+			ensure!(T::AllowDirectTransfer::get(), <Error<T>>::DirectTransferDisabled);
+
+			let (bounded_claim, proof, delegated) = Self::check_or_delegate(&sender, &claim)?;
+			ensure!(!Self::is_expired(&proof), <Error<T>>::ClaimExpired);
+			let from = proof.owner.clone();
 
-			<Proofs<T>>::insert(&bounded_claim, (&dest, <frame_system::Pallet<T>>::block_number()));
+			Self::do_transfer(&bounded_claim, proof, dest.clone())?;
 
-			Self::deposit_event(Event::ClaimTransfer(sender, claim, dest));
+			Self::deposit_event(Event::ClaimTransfer(from.clone(), claim.clone(), dest));
+			if delegated {
+				Self::deposit_event(Event::DelegateActed(sender, from, claim));
+			}
 
 			Ok(().into())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		pub fn check(
-			sender: &T::AccountId,
-			claim: &Vec<u8>,
-		) -> Result<BoundedVec<u8, T::MaxclaimLength>, Error<T>> {
+		/// Offers a claim to `dest`, which takes it over with `accept_transfer`. Replaces any
+		/// earlier offer.
+		#[pallet::weight(T::WeightInfo::propose_transfer(claim.len() as u32))]
+		pub fn propose_transfer(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			dest: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let (bounded_claim, proof, delegated) = Self::check_or_delegate(&sender, &claim)?;
+			ensure!(!Self::is_expired(&proof), <Error<T>>::ClaimExpired);
+			Self::ensure_transferable(&bounded_claim, &proof)?;
+
+			<PendingTransfers<T>>::insert(&bounded_claim, &dest);
+
+			Self::deposit_event(Event::TransferProposed(sender.clone(), claim.clone(), dest));
+			if delegated {
+				Self::deposit_event(Event::DelegateActed(sender, proof.owner, claim));
+			}
+
+			Ok(().into())
+		}
+
+		/// Takes over a claim offered to the sender.
+		#[pallet::weight(T::WeightInfo::accept_transfer(claim.len() as u32))]
+		pub fn accept_transfer(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
 			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
 				.map_err(|_| <Error<T>>::ClaimTooLong)?;
+			let dest =
+				<PendingTransfers<T>>::get(&bounded_claim).ok_or(<Error<T>>::NoPendingTransfer)?;
+			ensure!(dest == sender, <Error<T>>::NotTransferRecipient);
+
+			let proof = <Proofs<T>>::get(&bounded_claim).ok_or(<Error<T>>::ClaimNotExist)?;
+			ensure!(!Self::is_expired(&proof), <Error<T>>::ClaimExpired);
+			let from = proof.owner.clone();
+
+			Self::do_transfer(&bounded_claim, proof, sender.clone())?;
+
+			Self::deposit_event(Event::ClaimTransfer(from, claim, sender));
+
+			Ok(().into())
+		}
+
+		/// Withdraws an offer made with `propose_transfer`.
+		#[pallet::weight(T::WeightInfo::cancel_transfer(claim.len() as u32))]
+		pub fn cancel_transfer(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let (bounded_claim, proof, delegated) = Self::check_or_delegate(&sender, &claim)?;
+			ensure!(
+				<PendingTransfers<T>>::contains_key(&bounded_claim),
+				<Error<T>>::NoPendingTransfer
+			);
+
+			<PendingTransfers<T>>::remove(&bounded_claim);
+
+			Self::deposit_event(Event::TransferCancelled(sender.clone(), claim.clone()));
+			if delegated {
+				Self::deposit_event(Event::DelegateActed(sender, proof.owner, claim));
+			}
+
+			Ok(().into())
+		}
+
+		/// Anchors a batch of `leaf_count` documents by the root of their Merkle tree, see
+		/// [`crate::merkle`].
+		#[pallet::weight(T::WeightInfo::anchor_batch(metadata.len() as u32))]
+		pub fn anchor_batch(
+			origin: OriginFor<T>,
+			root: H256,
+			leaf_count: u32,
+			metadata: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(leaf_count > 0, <Error<T>>::EmptyBatch);
+			ensure!(!<Batches<T>>::contains_key(&root), <Error<T>>::BatchAlreadyAnchored);
+			let deposit = Self::deposit_for(root.as_bytes().len() + metadata.len());
+			let metadata = <BoundedVec<u8, T::MaxBatchMetadata>>::try_from(metadata)
+				.map_err(|_| <Error<T>>::MetadataTooLong)?;
+
+			T::Currency::reserve(&sender, deposit)?;
+
+			<Batches<T>>::insert(
+				&root,
+				BatchInfo {
+					owner: sender.clone(),
+					block_number: <frame_system::Pallet<T>>::block_number(),
+					leaf_count,
+					metadata,
+					deposit,
+				},
+			);
+
+			Self::deposit_event(Event::BatchAnchored(sender, root, leaf_count));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::create_digest_claim())]
+		pub fn create_digest_claim(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			digest: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
 
-			let owner = <Proofs<T>>::get(&bounded_claim).ok_or(<Error<T>>::ClaimNotExist)?.0;
+			Self::do_create_digest_claim(sender, algorithm, digest)?;
 
-			ensure!(owner == *sender, <Error<T>>::NotClaimOwner);
+			Ok(().into())
+		}
+
+		/// Creates a digest claim on `payload`, keeping `payload` itself out of state in the
+		/// offchain database of indexing nodes, see [`crate::payload`].
+		#[pallet::weight(T::WeightInfo::create_indexed_claim(payload.len() as u32))]
+		pub fn create_indexed_claim(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			payload: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(payload.len() as u32 <= T::MaxPayloadLength::get(), <Error<T>>::PayloadTooLong);
+			let digest = algorithm.hash(&payload);
+
+			Self::do_create_digest_claim(sender, algorithm, digest.clone())?;
+			sp_io::offchain_index::set(&crate::payload::key(algorithm, &digest), &payload);
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::revoke_digest_claim(reason.len() as u32))]
+		pub fn revoke_digest_claim(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			digest: Vec<u8>,
+			reason: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let (claim_digest, proof, delegated) =
+				Self::check_digest_or_delegate(&sender, algorithm, &digest)?;
+			let owner = proof.owner;
+			let bounded_reason = Self::bound_reason(&reason)?;
+
+			Self::note_history(
+				ClaimId::Digest(claim_digest.clone()),
+				ClaimChange::Revoked { owner: owner.clone() },
+			);
+			Self::retain_deposit(ClaimId::Digest(claim_digest.clone()), &owner, proof.deposit);
+			<DigestProofs<T>>::remove(&claim_digest);
+			<PendingDigestTransfers<T>>::remove(&claim_digest);
+			<ClaimsByOwner<T>>::remove(&owner, ClaimId::Digest(claim_digest.clone()));
+			Self::bury(
+				ClaimId::Digest(claim_digest),
+				owner.clone(),
+				sender.clone(),
+				bounded_reason,
+			);
+			sp_io::offchain_index::clear(&crate::payload::key(algorithm, &digest));
+
+			Self::deposit_event(Event::DigestClaimRevoked(
+				owner.clone(),
+				algorithm,
+				digest.clone(),
+				reason,
+			));
+			if delegated {
+				Self::deposit_event(Event::DelegateActed(sender, owner, digest));
+			}
+
+			Ok(().into())
+		}
+
+		/// Proposes a claim that becomes finalized once `threshold` of `cosigners` endorse it.
+		#[pallet::weight(T::WeightInfo::propose_cosigned_claim(claim.len() as u32, cosigners.len() as u32))]
+		pub fn propose_cosigned_claim(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			cosigners: Vec<T::AccountId>,
+			threshold: u32,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
+				.map_err(|_| <Error<T>>::ClaimTooLong)?;
+			Self::ensure_unclaimed(&bounded_claim)?;
+
+			// Bound the input before sorting it, so it cannot be arbitrarily long.
+			let cosigners = <BoundedVec<T::AccountId, T::MaxCosigners>>::try_from(cosigners)
+				.map_err(|_| <Error<T>>::TooManyCosigners)?;
+			let mut sorted = cosigners.to_vec();
+			sorted.sort();
+			sorted.dedup();
+			ensure!(sorted.len() == cosigners.len(), <Error<T>>::DuplicateCosigner);
+			ensure!(
+				threshold > 0 && threshold as usize <= cosigners.len(),
+				<Error<T>>::InvalidThreshold
+			);
+
+			let deposit = Self::claim_deposit_for(claim.len());
+			T::Currency::reserve(&sender, deposit)?;
+			Self::release_retained(&ClaimId::Raw(bounded_claim.clone()));
+
+			<CosignedClaims<T>>::insert(
+				&bounded_claim,
+				CosignedClaim {
+					proposer: sender.clone(),
+					block_number: <frame_system::Pallet<T>>::block_number(),
+					deposit,
+					cosigners,
+					endorsed: Default::default(),
+					threshold,
+					status: EndorsementStatus::Pending,
+				},
+			);
+
+			Self::deposit_event(Event::CosignedClaimProposed(sender, claim, threshold));
+
+			Ok(().into())
+		}
+
+		/// Endorses a co-signed claim the sender was asked to co-sign.
+		#[pallet::weight(T::WeightInfo::endorse_claim(claim.len() as u32, T::MaxCosigners::get()))]
+		pub fn endorse_claim(origin: OriginFor<T>, claim: Vec<u8>) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
+				.map_err(|_| <Error<T>>::ClaimTooLong)?;
+			let mut cosigned =
+				<CosignedClaims<T>>::get(&bounded_claim).ok_or(<Error<T>>::ClaimNotExist)?;
+
+			ensure!(cosigned.cosigners.contains(&sender), <Error<T>>::NotCosigner);
+			ensure!(!cosigned.endorsed.contains(&sender), <Error<T>>::AlreadyEndorsed);
+			// Cannot overflow: every endorser is a distinct co-signer.
+			cosigned.endorsed.try_push(sender.clone()).map_err(|_| <Error<T>>::TooManyCosigners)?;
+
+			Self::deposit_event(Event::ClaimEndorsed(sender, claim.clone()));
+
+			if cosigned.status == EndorsementStatus::Pending &&
+				cosigned.endorsed.len() as u32 >= cosigned.threshold
+			{
+				cosigned.status = EndorsementStatus::Finalized;
+				Self::note_history(
+					ClaimId::Raw(bounded_claim.clone()),
+					ClaimChange::Created { owner: cosigned.proposer.clone() },
+				);
+				Self::deposit_event(Event::ClaimFinalized(claim));
+			}
+			<CosignedClaims<T>>::insert(&bounded_claim, cosigned);
+
+			Ok(().into())
+		}
+
+		/// Withdraws a pending co-signed claim, releasing the proposer's deposit.
+		///
+		/// A finalized claim is attested by its co-signers, so only they can revoke it: it is
+		/// revoked once `threshold` of them called this, leaving a tombstone with the `reason`
+		/// of the last one, like [`Pallet::revoke_claim`].
+		#[pallet::weight(T::WeightInfo::revoke_cosigned_claim(
+			claim.len() as u32,
+			reason.len() as u32,
+		))]
+		pub fn revoke_cosigned_claim(
+			origin: OriginFor<T>,
+			claim: Vec<u8>,
+			reason: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
+				.map_err(|_| <Error<T>>::ClaimTooLong)?;
+			let cosigned =
+				<CosignedClaims<T>>::get(&bounded_claim).ok_or(<Error<T>>::ClaimNotExist)?;
+
+			if cosigned.status == EndorsementStatus::Pending {
+				ensure!(cosigned.proposer == sender, <Error<T>>::NotClaimOwner);
+				T::Currency::unreserve(&sender, cosigned.deposit);
+				<CosignedClaims<T>>::remove(&bounded_claim);
+				Self::deposit_event(Event::CosignedClaimRevoked(sender, claim));
+				return Ok(().into())
+			}
+
+			ensure!(cosigned.cosigners.contains(&sender), <Error<T>>::NotCosigner);
+			let bounded_reason = Self::bound_reason(&reason)?;
+			let mut approvals = <CosignedRevocations<T>>::get(&bounded_claim);
+			ensure!(!approvals.contains(&sender), <Error<T>>::RevocationAlreadyApproved);
+			// Cannot overflow: every approval is from a distinct co-signer.
+			approvals.try_push(sender.clone()).map_err(|_| <Error<T>>::TooManyCosigners)?;
+			Self::deposit_event(Event::CosignedRevocationApproved(sender.clone(), claim.clone()));
+			if (approvals.len() as u32) < cosigned.threshold {
+				<CosignedRevocations<T>>::insert(&bounded_claim, approvals);
+				return Ok(().into())
+			}
+
+			let claim_id = ClaimId::Raw(bounded_claim.clone());
+			Self::note_history(
+				claim_id.clone(),
+				ClaimChange::Revoked { owner: cosigned.proposer.clone() },
+			);
+			Self::retain_deposit(claim_id.clone(), &cosigned.proposer, cosigned.deposit);
+			<CosignedClaims<T>>::remove(&bounded_claim);
+			<CosignedRevocations<T>>::remove(&bounded_claim);
+			Self::bury(claim_id, cosigned.proposer, sender.clone(), bounded_reason);
+
+			Self::deposit_event(Event::CosignedClaimRevoked(sender, claim));
+
+			Ok(().into())
+		}
+
+		/// Moves a digest claim to `dest` without its consent. Only available while
+		/// `AllowDirectTransfer` is set.
+		#[pallet::weight(T::WeightInfo::transfer_digest_claim())]
+		pub fn transfer_digest_claim(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			digest: Vec<u8>,
+			dest: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(T::AllowDirectTransfer::get(), <Error<T>>::DirectTransferDisabled);
+
+			let (claim_digest, proof, delegated) =
+				Self::check_digest_or_delegate(&sender, algorithm, &digest)?;
+			let from = proof.owner.clone();
+
+			Self::do_transfer_digest(&claim_digest, proof, dest.clone())?;
+
+			Self::deposit_event(Event::DigestClaimTransfer(
+				from.clone(),
+				algorithm,
+				digest.clone(),
+				dest,
+			));
+			if delegated {
+				Self::deposit_event(Event::DelegateActed(sender, from, digest));
+			}
+
+			Ok(().into())
+		}
+
+		/// Creates an organization with the sender as its first admin.
+		#[pallet::weight(T::WeightInfo::create_org())]
+		pub fn create_org(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let deposit = T::OrgDeposit::get();
+			T::Currency::reserve(&sender, deposit)?;
+
+			let org = <NextOrgId<T>>::get();
+			<NextOrgId<T>>::put(org.saturating_add(1));
+			<Orgs<T>>::insert(
+				org,
+				OrgInfo { creator: sender.clone(), admins: 1, members: 1, deposit },
+			);
+			<OrgMembers<T>>::insert(org, &sender, OrgRole::Admin);
+
+			Self::deposit_event(Event::OrgCreated(org, sender));
+
+			Ok(().into())
+		}
+
+		/// Adds `who` to an organization or changes its role. Admins only. The admin adding a
+		/// member pays its deposit.
+		#[pallet::weight(T::WeightInfo::set_org_member())]
+		pub fn set_org_member(
+			origin: OriginFor<T>,
+			org: OrgId,
+			who: T::AccountId,
+			role: OrgRole,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let mut info = Self::ensure_org_admin(org, &sender)?;
+			let current = <OrgMembers<T>>::get(org, &who);
+			if current.is_none() {
+				ensure!(info.members < T::MaxOrgMembers::get(), <Error<T>>::TooManyOrgMembers);
+				let deposit = T::OrgMemberDeposit::get();
+				T::Currency::reserve(&sender, deposit)?;
+				<OrgMemberDeposits<T>>::insert(org, &who, (sender, deposit));
+				info.members = info.members.saturating_add(1);
+			}
+			match (current, role) {
+				(Some(OrgRole::Admin), OrgRole::Member) => {
+					ensure!(info.admins > 1, <Error<T>>::LastOrgAdmin);
+					info.admins -= 1;
+				},
+				(Some(OrgRole::Member) | None, OrgRole::Admin) =>
+					info.admins = info.admins.saturating_add(1),
+				_ => {},
+			}
+			<Orgs<T>>::insert(org, info);
+			<OrgMembers<T>>::insert(org, &who, role);
+
+			Self::deposit_event(Event::OrgMemberSet(org, who, role));
+
+			Ok(().into())
+		}
+
+		/// Removes `who` from an organization, returning its deposit to the admin that added it.
+		/// Admins only. Claims `who` created on behalf of the organization stay with it.
+		#[pallet::weight(T::WeightInfo::remove_org_member())]
+		pub fn remove_org_member(
+			origin: OriginFor<T>,
+			org: OrgId,
+			who: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let mut info = Self::ensure_org_admin(org, &sender)?;
+			let role = <OrgMembers<T>>::get(org, &who).ok_or(<Error<T>>::NotOrgMember)?;
+			if role == OrgRole::Admin {
+				ensure!(info.admins > 1, <Error<T>>::LastOrgAdmin);
+				info.admins -= 1;
+			}
+			info.members = info.members.saturating_sub(1);
+			<Orgs<T>>::insert(org, info);
+			<OrgMembers<T>>::remove(org, &who);
+			if let Some((depositor, deposit)) = <OrgMemberDeposits<T>>::take(org, &who) {
+				T::Currency::unreserve(&depositor, deposit);
+			}
+
+			Self::deposit_event(Event::OrgMemberRemoved(org, who));
+
+			Ok(().into())
+		}
+
+		/// Creates a claim owned by the account of `org`. The sender must be a member of it and
+		/// pays the deposit, but only admins of `org` can revoke or transfer the claim.
+		#[pallet::weight(T::WeightInfo::create_org_claim(
+			claim.len() as u32,
+			metadata.as_ref().map_or(0, |metadata| metadata.len() as u32),
+		))]
+		pub fn create_org_claim(
+			origin: OriginFor<T>,
+			org: OrgId,
+			claim: Vec<u8>,
+			metadata: Option<ClaimMetadata<Vec<u8>>>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Orgs<T>>::contains_key(org), <Error<T>>::OrgNotExist);
+			ensure!(<OrgMembers<T>>::contains_key(org, &sender), <Error<T>>::NotOrgMember);
+			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
+				.map_err(|_| <Error<T>>::ClaimTooLong)?;
+
+			Self::do_create_claim(Self::org_account(org), &sender, claim.clone(), None, metadata)?;
+			<OrgClaims<T>>::insert(&bounded_claim, (org, sender.clone()));
+
+			Self::deposit_event(Event::OrgClaimCreated(org, sender, claim));
+
+			Ok(().into())
+		}
+
+		#[pallet::weight(T::WeightInfo::add_issuer())]
+		pub fn add_issuer(origin: OriginFor<T>, issuer: T::AccountId) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(!<TrustedIssuers<T>>::contains_key(&issuer), <Error<T>>::AlreadyTrustedIssuer);
+			<TrustedIssuers<T>>::insert(&issuer, ());
+
+			Self::deposit_event(Event::IssuerAdded(issuer));
+
+			Ok(().into())
+		}
+
+		/// Stops trusting `issuer`. The credentials it issued are no longer valid, but stay
+		/// anchored.
+		#[pallet::weight(T::WeightInfo::remove_issuer())]
+		pub fn remove_issuer(origin: OriginFor<T>, issuer: T::AccountId) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(<TrustedIssuers<T>>::contains_key(&issuer), <Error<T>>::NotTrustedIssuer);
+			<TrustedIssuers<T>>::remove(&issuer);
+
+			Self::deposit_event(Event::IssuerRemoved(issuer));
+
+			Ok(().into())
+		}
+
+		/// Anchors the hash of a credential about `subject` as a claim of the issuer, which
+		/// cannot be transferred while it anchors the credential.
+		#[pallet::weight(T::WeightInfo::issue_credential())]
+		pub fn issue_credential(
+			origin: OriginFor<T>,
+			credential: H256,
+			subject: T::AccountId,
+			schema: H256,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<TrustedIssuers<T>>::contains_key(&sender), <Error<T>>::NotTrustedIssuer);
+			let claim = credential.as_bytes().to_vec();
+			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
+				.map_err(|_| <Error<T>>::ClaimTooLong)?;
+
+			Self::do_create_claim(sender.clone(), &sender, claim, expires_at, None)?;
+			<Credentials<T>>::insert(
+				&bounded_claim,
+				CredentialInfo {
+					issuer: sender.clone(),
+					subject: subject.clone(),
+					schema,
+					issued_at: <frame_system::Pallet<T>>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::CredentialIssued(sender, credential, subject, schema));
+
+			Ok(().into())
+		}
+
+		/// Revokes a credential and the claim anchoring it. Open to those that can revoke the
+		/// claim, as for `revoke_claim`, and to the subject.
+		#[pallet::weight(T::WeightInfo::revoke_credential(reason.len() as u32))]
+		pub fn revoke_credential(
+			origin: OriginFor<T>,
+			credential: H256,
+			reason: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let claim = credential.as_bytes().to_vec();
+			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
+				.map_err(|_| <Error<T>>::ClaimTooLong)?;
+			let info = <Credentials<T>>::get(&bounded_claim).ok_or(<Error<T>>::CredentialNotExist)?;
+			let proof = <Proofs<T>>::get(&bounded_claim)
+				.filter(|proof| proof.owner == info.issuer)
+				.ok_or(<Error<T>>::CredentialNotExist)?;
+			let delegated = if sender == info.subject {
+				false
+			} else {
+				let (_, _, delegated) = Self::check_or_delegate(&sender, &claim)
+					.map_err(|_| <Error<T>>::NotCredentialParty)?;
+				delegated
+			};
+
+			Self::do_revoke(bounded_claim, proof, sender.clone(), reason.clone())?;
+
+			Self::deposit_event(Event::CredentialRevoked(
+				sender.clone(),
+				info.issuer.clone(),
+				credential,
+				reason,
+			));
+			if delegated {
+				Self::deposit_event(Event::DelegateActed(sender, info.issuer, claim));
+			}
+
+			Ok(().into())
+		}
+
+		/// Creates every claim of `claims` without metadata.
+		///
+		/// The weight is benchmarked in [`BatchMode::BestEffort`], an upper bound for
+		/// [`BatchMode::Atomic`]. Only the claims processed before a failure are charged for.
+		#[pallet::weight(T::WeightInfo::create_claims(claims.len() as u32))]
+		pub fn create_claims(
+			origin: OriginFor<T>,
+			claims: Vec<Vec<u8>>,
+			mode: BatchMode,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Self::do_batch(&sender, claims, mode, T::WeightInfo::create_claims, |claim| {
+				Self::do_create_claim(sender.clone(), &sender, claim, None, None)
+			})
+		}
+
+		/// Revokes every claim of `claims`, leaving tombstones with the same `reason` behind.
+		///
+		/// Weighed like [`Pallet::create_claims`].
+		#[pallet::weight(T::WeightInfo::revoke_claims(claims.len() as u32, reason.len() as u32))]
+		pub fn revoke_claims(
+			origin: OriginFor<T>,
+			claims: Vec<Vec<u8>>,
+			reason: Vec<u8>,
+			mode: BatchMode,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let reason_len = reason.len() as u32;
+
+			let weight = |n| T::WeightInfo::revoke_claims(n, reason_len);
+			Self::do_batch(&sender, claims, mode, weight, |claim| {
+				let (bounded_claim, proof, delegated) = Self::check_or_delegate(&sender, &claim)?;
+				let owner = proof.owner.clone();
+				Self::do_revoke(bounded_claim, proof, sender.clone(), reason.clone())?;
+				if delegated {
+					Self::deposit_event(Event::DelegateActed(sender.clone(), owner, claim));
+				}
+				Ok(())
+			})
+		}
+
+		/// Lets `delegate` revoke and transfer the claims of the sender until `expires_at`, or
+		/// until removed, reserving `DelegateDeposit`. Replaces an earlier delegation of the same
+		/// account, keeping its deposit.
+		#[pallet::weight(T::WeightInfo::add_delegate())]
+		pub fn add_delegate(
+			origin: OriginFor<T>,
+			delegate: T::AccountId,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(delegate != sender, <Error<T>>::SelfDelegation);
+			if let Some(expires_at) = expires_at {
+				ensure!(
+					expires_at > <frame_system::Pallet<T>>::block_number(),
+					<Error<T>>::InvalidExpiry
+				);
+			}
+			let deposit = match <Delegates<T>>::get(&sender, &delegate) {
+				Some(info) => info.deposit,
+				None => {
+					let deposit = T::DelegateDeposit::get();
+					T::Currency::reserve(&sender, deposit)?;
+					deposit
+				},
+			};
+			<Delegates<T>>::insert(&sender, &delegate, DelegateInfo { expires_at, deposit });
+
+			Self::deposit_event(Event::DelegateAdded(sender, delegate, expires_at));
+
+			Ok(().into())
+		}
+
+		/// Withdraws the authorization of `delegate`, expired or not, returning its deposit.
+		#[pallet::weight(T::WeightInfo::remove_delegate())]
+		pub fn remove_delegate(
+			origin: OriginFor<T>,
+			delegate: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let info =
+				<Delegates<T>>::get(&sender, &delegate).ok_or(<Error<T>>::DelegateNotExist)?;
+			<Delegates<T>>::remove(&sender, &delegate);
+			T::Currency::unreserve(&sender, info.deposit);
+
+			Self::deposit_event(Event::DelegateRemoved(sender, delegate));
+
+			Ok(().into())
+		}
+
+		/// Removes an expired delegation of `owner`, returning its deposit. Callable by anyone.
+		#[pallet::weight(T::WeightInfo::remove_expired_delegate())]
+		pub fn remove_expired_delegate(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			delegate: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let info = <Delegates<T>>::get(&owner, &delegate).ok_or(<Error<T>>::DelegateNotExist)?;
+			ensure!(
+				info.expires_at.map_or(false, |at| at <= <frame_system::Pallet<T>>::block_number()),
+				<Error<T>>::DelegateNotExpired
+			);
+			<Delegates<T>>::remove(&owner, &delegate);
+			T::Currency::unreserve(&owner, info.deposit);
+
+			Self::deposit_event(Event::DelegateRemoved(owner, delegate));
+
+			Ok(().into())
+		}
+
+		/// Offers a digest claim to `dest`, which takes it over with `accept_digest_transfer`.
+		/// Replaces any earlier offer.
+		#[pallet::weight(T::WeightInfo::propose_digest_transfer())]
+		pub fn propose_digest_transfer(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			digest: Vec<u8>,
+			dest: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let (claim_digest, proof, delegated) =
+				Self::check_digest_or_delegate(&sender, algorithm, &digest)?;
+
+			<PendingDigestTransfers<T>>::insert(&claim_digest, &dest);
+
+			Self::deposit_event(Event::DigestTransferProposed(
+				sender.clone(),
+				algorithm,
+				digest.clone(),
+				dest,
+			));
+			if delegated {
+				Self::deposit_event(Event::DelegateActed(sender, proof.owner, digest));
+			}
+
+			Ok(().into())
+		}
+
+		/// Takes over a digest claim offered to the sender.
+		#[pallet::weight(T::WeightInfo::accept_digest_transfer())]
+		pub fn accept_digest_transfer(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			digest: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let claim_digest = Self::claim_digest(algorithm, &digest)?;
+			let dest = <PendingDigestTransfers<T>>::get(&claim_digest)
+				.ok_or(<Error<T>>::NoPendingTransfer)?;
+			ensure!(dest == sender, <Error<T>>::NotTransferRecipient);
+
+			let proof = <DigestProofs<T>>::get(&claim_digest).ok_or(<Error<T>>::ClaimNotExist)?;
+			let from = proof.owner.clone();
+
+			Self::do_transfer_digest(&claim_digest, proof, sender.clone())?;
+
+			Self::deposit_event(Event::DigestClaimTransfer(from, algorithm, digest, sender));
+
+			Ok(().into())
+		}
+
+		/// Withdraws an offer made with `propose_digest_transfer`.
+		#[pallet::weight(T::WeightInfo::cancel_digest_transfer())]
+		pub fn cancel_digest_transfer(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			digest: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let (claim_digest, proof, delegated) =
+				Self::check_digest_or_delegate(&sender, algorithm, &digest)?;
+			ensure!(
+				<PendingDigestTransfers<T>>::contains_key(&claim_digest),
+				<Error<T>>::NoPendingTransfer
+			);
+
+			<PendingDigestTransfers<T>>::remove(&claim_digest);
+
+			Self::deposit_event(Event::DigestTransferCancelled(
+				sender.clone(),
+				algorithm,
+				digest.clone(),
+			));
+			if delegated {
+				Self::deposit_event(Event::DelegateActed(sender, proof.owner, digest));
+			}
+
+			Ok(().into())
+		}
+
+		/// Removes a batch anchored by the caller, returning its deposit. Inclusion proofs
+		/// against `root` no longer verify afterwards.
+		#[pallet::weight(T::WeightInfo::remove_batch())]
+		pub fn remove_batch(origin: OriginFor<T>, root: H256) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let info = <Batches<T>>::get(&root).ok_or(<Error<T>>::BatchNotExist)?;
+			ensure!(info.owner == sender, <Error<T>>::NotClaimOwner);
+			<Batches<T>>::remove(&root);
+			T::Currency::unreserve(&sender, info.deposit);
+
+			Self::deposit_event(Event::BatchRemoved(sender, root));
+
+			Ok(().into())
+		}
+
+		/// Removes the history of a claim revoked or expired at least `TombstoneLifetime` blocks
+		/// ago, returning the deposit kept for it to the last owner. The tombstone of a revoked
+		/// claim stays, so it still reads as revoked and cannot be recreated unless
+		/// `AllowRecreateRevoked`. Callable by anyone.
+		#[pallet::weight(T::WeightInfo::purge_claim_history())]
+		pub fn purge_claim_history(
+			origin: OriginFor<T>,
+			claim_id: ClaimId<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let ended_at = <ClaimHistory<T>>::get(&claim_id)
+				.last()
+				.filter(|entry| {
+					matches!(entry.change, ClaimChange::Revoked { .. } | ClaimChange::Expired { .. })
+				})
+				.map(|entry| entry.block_number)
+				.ok_or(<Error<T>>::ClaimNotEnded)?;
+			ensure!(
+				ended_at.saturating_add(T::TombstoneLifetime::get()) <=
+					<frame_system::Pallet<T>>::block_number(),
+				<Error<T>>::TombstoneTooRecent
+			);
+
+			<ClaimHistory<T>>::remove(&claim_id);
+			Self::release_retained(&claim_id);
+
+			Self::deposit_event(Event::ClaimHistoryPurged(claim_id));
+
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Runs `f` on each of `claims`. In [`BatchMode::BestEffort`] the changes of a failing
+		/// claim are rolled back and the others kept. Returns `weight` of the number of claims
+		/// processed, also when failing.
+		fn do_batch(
+			sender: &T::AccountId,
+			claims: Vec<Vec<u8>>,
+			mode: BatchMode,
+			weight: impl Fn(u32) -> Weight,
+			f: impl Fn(Vec<u8>) -> DispatchResult,
+		) -> DispatchResultWithPostInfo {
+			if claims.len() as u32 > T::MaxBatchSize::get() {
+				return Err(<Error<T>>::BatchTooLarge.with_weight(weight(0)))
+			}
+
+			let count = claims.len() as u32;
+			for (index, claim) in claims.into_iter().enumerate() {
+				match mode {
+					BatchMode::Atomic =>
+						f(claim).map_err(|e| e.with_weight(weight(index as u32 + 1)))?,
+					BatchMode::BestEffort => {
+						let result = with_transaction(|| {
+							let result = f(claim);
+							if result.is_ok() {
+								TransactionOutcome::Commit(result)
+							} else {
+								TransactionOutcome::Rollback(result)
+							}
+						});
+						Self::deposit_event(Event::ClaimBatchItem(
+							sender.clone(),
+							index as u32,
+							result,
+						));
+					},
+				}
+			}
+			Ok(Some(weight(count)).into())
+		}
+
+		/// Creates a claim of `owner`, reserving its deposit from `depositor`.
+		fn do_create_claim(
+			owner: T::AccountId,
+			depositor: &T::AccountId,
+			claim: Vec<u8>,
+			expires_at: Option<T::BlockNumber>,
+			metadata: Option<ClaimMetadata<Vec<u8>>>,
+		) -> DispatchResult {
+			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
+				.map_err(|_| <Error<T>>::ClaimTooLong)?;
+
+			Self::ensure_unclaimed(&bounded_claim)?;
+			Self::ensure_valid_expiry(expires_at)?;
+			let bounded_metadata = metadata.clone().map(Self::bound_metadata).transpose()?;
+
+			let deposit = Self::claim_deposit_for(
+				claim.len() + metadata.as_ref().map_or(0, |metadata| metadata.len()),
+			);
+			T::Currency::reserve(depositor, deposit)?;
+			Self::note_history(
+				ClaimId::Raw(bounded_claim.clone()),
+				ClaimChange::Created { owner: owner.clone() },
+			);
+
+			if let Some(bounded_metadata) = bounded_metadata {
+				<ClaimMetadataOf<T>>::insert(&bounded_claim, bounded_metadata);
+			}
+
+			if let Some(expires_at) = expires_at {
+				<Expiries<T>>::insert(expires_at, &bounded_claim, ());
+			}
+			<Proofs<T>>::insert(
+				&bounded_claim,
+				Self::new_proof(owner.clone(), deposit, expires_at),
+			);
+			<Revoked<T>>::remove(ClaimId::Raw(bounded_claim.clone()));
+			Self::release_retained(&ClaimId::Raw(bounded_claim.clone()));
+			<ClaimsByOwner<T>>::insert(&owner, ClaimId::Raw(bounded_claim), ());
+
+			Self::deposit_event(Event::ClaimCreated(owner, claim, metadata));
+
+			Ok(())
+		}
+
+		fn do_create_digest_claim(
+			sender: T::AccountId,
+			algorithm: HashAlgorithm,
+			digest: Vec<u8>,
+		) -> DispatchResult {
+			let claim_digest = Self::claim_digest(algorithm, &digest)?;
+
+			ensure!(!<DigestProofs<T>>::contains_key(&claim_digest), <Error<T>>::ProofAlreadyExist);
+			Self::ensure_recreatable(&ClaimId::Digest(claim_digest.clone()))?;
+
+			let deposit = Self::claim_deposit_for(digest.len());
+			T::Currency::reserve(&sender, deposit)?;
+			Self::note_history(
+				ClaimId::Digest(claim_digest.clone()),
+				ClaimChange::Created { owner: sender.clone() },
+			);
+
+			<DigestProofs<T>>::insert(
+				&claim_digest,
+				Self::new_proof(sender.clone(), deposit, None),
+			);
+			<Revoked<T>>::remove(ClaimId::Digest(claim_digest.clone()));
+			Self::release_retained(&ClaimId::Digest(claim_digest.clone()));
+			<ClaimsByOwner<T>>::insert(&sender, ClaimId::Digest(claim_digest), ());
+
+			Self::deposit_event(Event::DigestClaimCreated(sender, algorithm, digest));
+
+			Ok(())
+		}
+
+		/// Removes a claim, leaving a tombstone signed by `revoked_by` behind.
+		fn do_revoke(
+			bounded_claim: BoundedVec<u8, T::MaxclaimLength>,
+			proof: ProofInfoOf<T>,
+			revoked_by: T::AccountId,
+			reason: Vec<u8>,
+		) -> DispatchResult {
+			let bounded_reason = Self::bound_reason(&reason)?;
+
+			Self::note_history(
+				ClaimId::Raw(bounded_claim.clone()),
+				ClaimChange::Revoked { owner: proof.owner.clone() },
+			);
+			let depositor = Self::depositor(&bounded_claim, &proof);
+			Self::retain_deposit(ClaimId::Raw(bounded_claim.clone()), &depositor, proof.deposit);
+			if let Some(expires_at) = proof.expires_at {
+				<Expiries<T>>::remove(expires_at, &bounded_claim);
+			}
+			<Proofs<T>>::remove(&bounded_claim);
+			<PendingTransfers<T>>::remove(&bounded_claim);
+			<ClaimMetadataOf<T>>::remove(&bounded_claim);
+			<OrgClaims<T>>::remove(&bounded_claim);
+			<ClaimsByOwner<T>>::remove(&proof.owner, ClaimId::Raw(bounded_claim.clone()));
+			Self::bury(
+				ClaimId::Raw(bounded_claim.clone()),
+				proof.owner.clone(),
+				revoked_by,
+				bounded_reason,
+			);
+
+			Self::deposit_event(Event::ClaimRevoked(proof.owner, bounded_claim.into_inner(), reason));
+
+			Ok(())
+		}
+
+		fn bound_metadata(
+			metadata: ClaimMetadata<Vec<u8>>,
+		) -> Result<BoundedClaimMetadata<T>, Error<T>> {
+			let bound = |field: Vec<u8>| {
+				<BoundedVec<u8, T::MaxMetadataLength>>::try_from(field)
+					.map_err(|_| <Error<T>>::MetadataTooLong)
+			};
+			Ok(ClaimMetadata {
+				title: bound(metadata.title)?,
+				mime_type: bound(metadata.mime_type)?,
+				uri: bound(metadata.uri)?,
+			})
+		}
+
+		/// Hands a claim and its deposit over to `dest`, dropping any pending offer.
+		fn do_transfer(
+			bounded_claim: &BoundedVec<u8, T::MaxclaimLength>,
+			proof: ProofInfoOf<T>,
+			dest: T::AccountId,
+		) -> DispatchResult {
+			Self::ensure_transferable(bounded_claim, &proof)?;
+			Self::note_history(
+				ClaimId::Raw(bounded_claim.clone()),
+				ClaimChange::Transferred { from: proof.owner.clone(), to: dest.clone() },
+			);
+			let depositor = Self::depositor(bounded_claim, &proof);
+			let deposit = Self::move_deposit(&depositor, &dest, proof.deposit)?;
+
+			<ClaimsByOwner<T>>::remove(&proof.owner, ClaimId::Raw(bounded_claim.clone()));
+			<ClaimsByOwner<T>>::insert(&dest, ClaimId::Raw(bounded_claim.clone()), ());
+			<Proofs<T>>::insert(bounded_claim, Self::new_proof(dest, deposit, proof.expires_at));
+			<PendingTransfers<T>>::remove(bounded_claim);
+			<OrgClaims<T>>::remove(bounded_claim);
+
+			Ok(())
+		}
+
+		fn do_transfer_digest(
+			claim_digest: &ClaimDigest,
+			proof: ProofInfoOf<T>,
+			dest: T::AccountId,
+		) -> DispatchResult {
+			Self::note_history(
+				ClaimId::Digest(claim_digest.clone()),
+				ClaimChange::Transferred { from: proof.owner.clone(), to: dest.clone() },
+			);
+			let deposit = Self::move_deposit(&proof.owner, &dest, proof.deposit)?;
+
+			<ClaimsByOwner<T>>::remove(&proof.owner, ClaimId::Digest(claim_digest.clone()));
+			<ClaimsByOwner<T>>::insert(&dest, ClaimId::Digest(claim_digest.clone()), ());
+			<DigestProofs<T>>::insert(
+				claim_digest,
+				Self::new_proof(dest, deposit, proof.expires_at),
+			);
+			<PendingDigestTransfers<T>>::remove(claim_digest);
+
+			Ok(())
+		}
+
+		/// Appends `change` to the history of a claim, dropping its oldest entries once it holds
+		/// `MaxHistoryLength` of them.
+		fn note_history(claim_id: ClaimId<T>, change: ClaimChange<T::AccountId>) {
+			let entry = HistoryEntry {
+				change,
+				block_number: <frame_system::Pallet<T>>::block_number(),
+				moment: <pallet_timestamp::Pallet<T>>::get(),
+			};
+			<ClaimHistory<T>>::mutate(claim_id, |history| {
+				while !history.is_empty() && history.len() as u32 >= T::MaxHistoryLength::get() {
+					history.remove(0);
+				}
+				// Only fails if `MaxHistoryLength` is zero, which keeps no history at all.
+				let _ = history.try_push(entry);
+			})
+		}
+
+		fn ensure_org_admin(org: OrgId, who: &T::AccountId) -> Result<OrgInfoOf<T>, Error<T>> {
+			let info = <Orgs<T>>::get(org).ok_or(<Error<T>>::OrgNotExist)?;
+			ensure!(<OrgMembers<T>>::get(org, who) == Some(OrgRole::Admin), <Error<T>>::NotOrgAdmin);
+			Ok(info)
+		}
+
+		/// Ensures `bounded_claim` does not anchor a credential of its owner.
+		fn ensure_transferable(
+			bounded_claim: &BoundedVec<u8, T::MaxclaimLength>,
+			proof: &ProofInfoOf<T>,
+		) -> DispatchResult {
+			ensure!(
+				!<Credentials<T>>::get(bounded_claim).map_or(false, |info| info.issuer == proof.owner),
+				<Error<T>>::CredentialNotTransferable
+			);
+			Ok(())
+		}
+
+		/// The account owning the claims of `org`. Nobody holds its key.
+		pub fn org_account(org: OrgId) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating(org)
+		}
+
+		/// The account the deposit of a raw claim is reserved from: the member that submitted
+		/// a claim of an organization, otherwise its owner.
+		fn depositor(
+			bounded_claim: &BoundedVec<u8, T::MaxclaimLength>,
+			proof: &ProofInfoOf<T>,
+		) -> T::AccountId {
+			<OrgClaims<T>>::get(bounded_claim)
+				.map_or_else(|| proof.owner.clone(), |(_, submitter)| submitter)
+		}
+
+		/// Ensures `bounded_claim` is neither a single-owner nor a co-signed claim, and may be
+		/// created if it was revoked.
+		fn ensure_unclaimed(bounded_claim: &BoundedVec<u8, T::MaxclaimLength>) -> DispatchResult {
+			ensure!(
+				!<Proofs<T>>::contains_key(bounded_claim) &&
+					!<CosignedClaims<T>>::contains_key(bounded_claim),
+				<Error<T>>::ProofAlreadyExist
+			);
+			Self::ensure_recreatable(&ClaimId::Raw(bounded_claim.clone()))
+		}
+
+		fn ensure_recreatable(claim_id: &ClaimId<T>) -> DispatchResult {
+			ensure!(
+				T::AllowRecreateRevoked::get() || !<Revoked<T>>::contains_key(claim_id),
+				<Error<T>>::ClaimWasRevoked
+			);
+			Ok(())
+		}
+
+		fn bound_reason(reason: &[u8]) -> Result<BoundedVec<u8, T::MaxReasonLength>, Error<T>> {
+			<BoundedVec<u8, T::MaxReasonLength>>::try_from(reason.to_vec())
+				.map_err(|_| <Error<T>>::ReasonTooLong)
+		}
+
+		/// Leaves the tombstone of a revoked claim.
+		fn bury(
+			claim_id: ClaimId<T>,
+			owner: T::AccountId,
+			revoked_by: T::AccountId,
+			reason: BoundedVec<u8, T::MaxReasonLength>,
+		) {
+			<Revoked<T>>::insert(
+				claim_id,
+				RevocationInfo {
+					owner,
+					revoked_by,
+					block_number: <frame_system::Pallet<T>>::block_number(),
+					reason,
+				},
+			);
+		}
+
+		/// Releases the deposit of a revoked or expired claim, but for up to `HistoryDeposit`
+		/// kept until its history and tombstone are purged or the claim is created again.
+		fn retain_deposit(claim_id: ClaimId<T>, owner: &T::AccountId, deposit: BalanceOf<T>) {
+			let retained = deposit.min(T::HistoryDeposit::get());
+			T::Currency::unreserve(owner, deposit - retained);
+			if !retained.is_zero() {
+				<RetainedDeposits<T>>::insert(claim_id, (owner.clone(), retained));
+			}
+		}
+
+		fn release_retained(claim_id: &ClaimId<T>) {
+			if let Some((owner, deposit)) = <RetainedDeposits<T>>::take(claim_id) {
+				T::Currency::unreserve(&owner, deposit);
+			}
+		}
+
+		/// Whether `leaf` belongs to the batch anchored at `root`, served by the runtime API.
+		pub fn verify_inclusion(leaf: H256, proof: Vec<H256>, root: H256) -> bool {
+			<Batches<T>>::contains_key(&root) && crate::merkle::verify(leaf, &proof, root)
+		}
+
+		/// Looks up a single-owner claim by its raw bytes, then as a digest under every
+		/// algorithm producing digests of that length. Served by the runtime API.
+		pub fn claim_record(claim: Vec<u8>) -> Option<ClaimRecordOf<T>> {
+			if let Ok(bounded_claim) = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone()) {
+				if let Some(proof) = <Proofs<T>>::get(&bounded_claim) {
+					let org = <OrgClaims<T>>::get(&bounded_claim);
+					return Some(Self::record(claim, None, proof, org))
+				}
+			}
+			[HashAlgorithm::Blake2_256, HashAlgorithm::Sha2_256, HashAlgorithm::Keccak256]
+				.into_iter()
+				.find_map(|algorithm| {
+					let claim_digest = Self::claim_digest(algorithm, &claim).ok()?;
+					let proof = <DigestProofs<T>>::get(&claim_digest)?;
+					Some(Self::record(claim.clone(), Some(algorithm), proof, None))
+				})
+		}
+
+		/// Every single-owner claim of `owner`, served by the runtime API.
+		pub fn claims_by_owner(owner: T::AccountId) -> Vec<ClaimRecordOf<T>> {
+			<ClaimsByOwner<T>>::iter_key_prefix(&owner)
+				.filter_map(|claim_id| match claim_id {
+					ClaimId::Raw(bounded_claim) => <Proofs<T>>::get(&bounded_claim).map(|proof| {
+						let org = <OrgClaims<T>>::get(&bounded_claim);
+						Self::record(bounded_claim.into_inner(), None, proof, org)
+					}),
+					ClaimId::Digest(claim_digest) => <DigestProofs<T>>::get(&claim_digest).map(|proof| {
+						Self::record(
+							claim_digest.digest.into_inner(),
+							Some(claim_digest.algorithm),
+							proof,
+							None,
+						)
+					}),
+				})
+				.collect()
+		}
+
+		fn record(
+			claim: Vec<u8>,
+			algorithm: Option<HashAlgorithm>,
+			proof: ProofInfoOf<T>,
+			org: Option<(OrgId, T::AccountId)>,
+		) -> ClaimRecordOf<T> {
+			let (org, submitter) = match org {
+				Some((org, submitter)) => (Some(org), Some(submitter)),
+				None => (None, None),
+			};
+			ClaimRecord {
+				claim,
+				algorithm,
+				owner: proof.owner,
+				block_number: proof.block_number,
+				moment: proof.moment,
+				expires_at: proof.expires_at,
+				org,
+				submitter,
+			}
+		}
+
+		/// Where a single-owner claim looked up like in [`Self::claim_record`] was last set.
+		/// Served by the runtime API.
+		pub fn proof_anchor(claim: Vec<u8>) -> Option<ProofAnchorOf<T>> {
+			let anchor = |key, proof: ProofInfoOf<T>| ProofAnchor {
+				key,
+				block_number: proof.block_number,
+				moment: proof.moment,
+				extrinsic_index: proof.extrinsic_index,
+			};
+			Self::find_by_claim(&claim, |claim_id| match claim_id {
+				ClaimId::Raw(bounded_claim) => <Proofs<T>>::get(&bounded_claim)
+					.map(|proof| anchor(<Proofs<T>>::hashed_key_for(&bounded_claim), proof)),
+				ClaimId::Digest(claim_digest) => <DigestProofs<T>>::get(&claim_digest)
+					.map(|proof| anchor(<DigestProofs<T>>::hashed_key_for(&claim_digest), proof)),
+			})
+		}
+
+		/// History of a single-owner claim looked up like in [`Self::claim_record`], including
+		/// claims that no longer exist. Served by the runtime API.
+		pub fn claim_history(claim: Vec<u8>) -> Vec<HistoryEntryOf<T>> {
+			Self::find_by_claim(&claim, |claim_id| {
+				let history = <ClaimHistory<T>>::get(claim_id);
+				(!history.is_empty()).then(|| history.into_inner())
+			})
+			.unwrap_or_default()
+		}
+
+		/// Tombstone of a revoked claim looked up like in [`Self::claim_record`], `None` if the
+		/// claim was never revoked or was created again.
+		/// Served by the runtime API.
+		pub fn revocation(
+			claim: Vec<u8>,
+		) -> Option<RevocationInfo<T::AccountId, T::BlockNumber, Vec<u8>>> {
+			Self::find_by_claim(&claim, |claim_id| <Revoked<T>>::get(claim_id))
+				.map(Self::unbound_revocation)
+		}
+
+		fn unbound_revocation(
+			info: RevocationInfoOf<T>,
+		) -> RevocationInfo<T::AccountId, T::BlockNumber, Vec<u8>> {
+			RevocationInfo {
+				owner: info.owner,
+				revoked_by: info.revoked_by,
+				block_number: info.block_number,
+				reason: info.reason.into_inner(),
+			}
+		}
+
+		/// The first result of `f` for `claim` as raw bytes, then as a digest under every
+		/// algorithm producing digests of that length.
+		fn find_by_claim<R>(claim: &[u8], f: impl Fn(ClaimId<T>) -> Option<R>) -> Option<R> {
+			if let Ok(bounded_claim) = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.to_vec()) {
+				if let Some(found) = f(ClaimId::Raw(bounded_claim)) {
+					return Some(found)
+				}
+			}
+			[HashAlgorithm::Blake2_256, HashAlgorithm::Sha2_256, HashAlgorithm::Keccak256]
+				.into_iter()
+				.filter_map(|algorithm| Self::claim_digest(algorithm, claim).ok())
+				.find_map(|claim_digest| f(ClaimId::Digest(claim_digest)))
+		}
+
+		/// Status of the credential with hash `credential`, served by the runtime API.
+		pub fn credential_status(
+			credential: H256,
+		) -> Option<CredentialStatus<T::AccountId, T::BlockNumber>> {
+			let bounded_claim =
+				<BoundedVec<u8, T::MaxclaimLength>>::try_from(credential.as_bytes().to_vec()).ok()?;
+			let info = <Credentials<T>>::get(&bounded_claim)?;
+			let proof = <Proofs<T>>::get(&bounded_claim).filter(|proof| proof.owner == info.issuer);
+			let valid = <TrustedIssuers<T>>::contains_key(&info.issuer) &&
+				proof.as_ref().map_or(false, |proof| !Self::is_expired(proof));
+			let revocation = if proof.is_none() {
+				<Revoked<T>>::get(ClaimId::Raw(bounded_claim))
+					.filter(|revocation| revocation.owner == info.issuer)
+					.map(Self::unbound_revocation)
+			} else {
+				None
+			};
+			Some(CredentialStatus {
+				issuer: info.issuer,
+				subject: info.subject,
+				schema: info.schema,
+				issued_at: info.issued_at,
+				expires_at: proof.and_then(|proof| proof.expires_at),
+				revocation,
+				valid,
+			})
+		}
+
+		/// Endorsement status of a co-signed claim, served by the runtime API.
+		pub fn endorsement_status(claim: Vec<u8>) -> Option<EndorsementInfo<T::AccountId>> {
+			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim).ok()?;
+			let cosigned = <CosignedClaims<T>>::get(&bounded_claim)?;
+			let pending = cosigned
+				.cosigners
+				.iter()
+				.filter(|cosigner| !cosigned.endorsed.contains(cosigner))
+				.cloned()
+				.collect();
+			Some(EndorsementInfo {
+				status: cosigned.status,
+				threshold: cosigned.threshold,
+				endorsed: cosigned.endorsed.into_inner(),
+				pending,
+			})
+		}
+
+		fn ensure_valid_expiry(expires_at: Option<T::BlockNumber>) -> Result<(), Error<T>> {
+			if let Some(expires_at) = expires_at {
+				ensure!(
+					expires_at > <frame_system::Pallet<T>>::block_number(),
+					<Error<T>>::InvalidExpiry
+				);
+			}
+			Ok(())
+		}
+
+		/// What is recorded for a claim set by the current extrinsic.
+		fn new_proof(
+			owner: T::AccountId,
+			deposit: BalanceOf<T>,
+			expires_at: Option<T::BlockNumber>,
+		) -> ProofInfoOf<T> {
+			ProofInfo {
+				owner,
+				block_number: <frame_system::Pallet<T>>::block_number(),
+				moment: <pallet_timestamp::Pallet<T>>::get(),
+				extrinsic_index: <frame_system::Pallet<T>>::extrinsic_index(),
+				deposit,
+				expires_at,
+			}
+		}
+
+		/// Whether the claim has expired, whether or not it has been purged yet.
+		pub fn is_expired(proof: &ProofInfoOf<T>) -> bool {
+			proof
+				.expires_at
+				.map_or(false, |expires_at| expires_at <= <frame_system::Pallet<T>>::block_number())
+		}
+
+		/// Removes a claim scheduled to expire at `at`, releasing its deposit but for the part
+		/// kept with its history.
+		pub(crate) fn expire(at: T::BlockNumber, bounded_claim: BoundedVec<u8, T::MaxclaimLength>) {
+			<Expiries<T>>::remove(at, &bounded_claim);
+			match <Proofs<T>>::get(&bounded_claim) {
+				Some(proof) if proof.expires_at == Some(at) => {
+					Self::note_history(
+						ClaimId::Raw(bounded_claim.clone()),
+						ClaimChange::Expired { owner: proof.owner.clone() },
+					);
+					Self::retain_deposit(
+						ClaimId::Raw(bounded_claim.clone()),
+						&Self::depositor(&bounded_claim, &proof),
+						proof.deposit,
+					);
+					<Proofs<T>>::remove(&bounded_claim);
+					<PendingTransfers<T>>::remove(&bounded_claim);
+					<ClaimMetadataOf<T>>::remove(&bounded_claim);
+					<OrgClaims<T>>::remove(&bounded_claim);
+					<ClaimsByOwner<T>>::remove(&proof.owner, ClaimId::Raw(bounded_claim.clone()));
+					Self::deposit_event(Event::ClaimExpired(proof.owner, bounded_claim.into_inner()));
+				},
+				_ => {},
+			}
+		}
+
+		pub fn check(
+			sender: &T::AccountId,
+			claim: &Vec<u8>,
+		) -> Result<(BoundedVec<u8, T::MaxclaimLength>, ProofInfoOf<T>), Error<T>> {
+			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
+				.map_err(|_| <Error<T>>::ClaimTooLong)?;
+
+			let proof = <Proofs<T>>::get(&bounded_claim).ok_or(<Error<T>>::ClaimNotExist)?;
+
+			match <OrgClaims<T>>::get(&bounded_claim) {
+				Some((org, _)) => ensure!(
+					<OrgMembers<T>>::get(org, sender) == Some(OrgRole::Admin),
+					<Error<T>>::NotClaimOwner
+				),
+				None => ensure!(proof.owner == *sender, <Error<T>>::NotClaimOwner),
+			}
+
+			Ok((bounded_claim, proof))
+		}
+
+		/// Like [`Self::check`], but also accepts an active delegate of the owner of a claim
+		/// not held by an organization. Returns whether `sender` acts as a delegate.
+		pub fn check_or_delegate(
+			sender: &T::AccountId,
+			claim: &Vec<u8>,
+		) -> Result<(BoundedVec<u8, T::MaxclaimLength>, ProofInfoOf<T>, bool), Error<T>> {
+			match Self::check(sender, claim) {
+				Err(<Error<T>>::NotClaimOwner) => {},
+				checked => return checked.map(|(bounded_claim, proof)| (bounded_claim, proof, false)),
+			}
+
+			let bounded_claim = <BoundedVec<u8, T::MaxclaimLength>>::try_from(claim.clone())
+				.map_err(|_| <Error<T>>::ClaimTooLong)?;
+			let proof = <Proofs<T>>::get(&bounded_claim).ok_or(<Error<T>>::ClaimNotExist)?;
+			ensure!(
+				!<OrgClaims<T>>::contains_key(&bounded_claim) &&
+					Self::is_delegate(&proof.owner, sender),
+				<Error<T>>::NotClaimOwner
+			);
+
+			Ok((bounded_claim, proof, true))
+		}
+
+		/// Whether `delegate` may currently revoke and transfer the claims of `owner`, or
+		/// propose and cancel their transfer.
+		pub fn is_delegate(owner: &T::AccountId, delegate: &T::AccountId) -> bool {
+			<Delegates<T>>::get(owner, delegate).map_or(false, |info| {
+				info.expires_at.map_or(true, |at| at > <frame_system::Pallet<T>>::block_number())
+			})
+		}
+
+		/// The deposit for a claim of `len` bytes.
+		pub fn deposit_for(len: usize) -> BalanceOf<T> {
+			T::ClaimDeposit::get().saturating_mul((len as u32).into())
+		}
+
+		/// The deposit for a single-owner claim of `len` bytes, including its history.
+		pub fn claim_deposit_for(len: usize) -> BalanceOf<T> {
+			Self::deposit_for(len).saturating_add(T::HistoryDeposit::get())
+		}
+
+		/// Moves a claim deposit from `from` to `to`, keeping it reserved. Returns the amount
+		/// actually moved.
+		fn move_deposit(
+			from: &T::AccountId,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			if amount.is_zero() || from == to {
+				return Ok(amount)
+			}
+			let remaining = T::Currency::repatriate_reserved(from, to, amount, BalanceStatus::Reserved)?;
+			Ok(amount.saturating_sub(remaining))
+		}
+
+		/// Builds the storage key of a digest claim, validating the digest length against
+		/// the algorithm.
+		pub fn claim_digest(algorithm: HashAlgorithm, digest: &[u8]) -> Result<ClaimDigest, Error<T>> {
+			ensure!(digest.len() as u32 == algorithm.digest_len(), <Error<T>>::InvalidDigestLength);
+
+			let digest = <BoundedVec<u8, MaxDigestLength>>::try_from(digest.to_vec())
+				.map_err(|_| <Error<T>>::InvalidDigestLength)?;
+
+			Ok(ClaimDigest { algorithm, digest })
+		}
+
+		pub fn check_digest(
+			sender: &T::AccountId,
+			algorithm: HashAlgorithm,
+			digest: &[u8],
+		) -> Result<(ClaimDigest, ProofInfoOf<T>), Error<T>> {
+			let claim_digest = Self::claim_digest(algorithm, digest)?;
+
+			let proof = <DigestProofs<T>>::get(&claim_digest).ok_or(<Error<T>>::ClaimNotExist)?;
+
+			ensure!(proof.owner == *sender, <Error<T>>::NotClaimOwner);
+
+			Ok((claim_digest, proof))
+		}
+
+		/// Like [`Self::check_digest`], but also accepts an active delegate of the owner.
+		/// Returns whether `sender` acts as a delegate.
+		pub fn check_digest_or_delegate(
+			sender: &T::AccountId,
+			algorithm: HashAlgorithm,
+			digest: &[u8],
+		) -> Result<(ClaimDigest, ProofInfoOf<T>, bool), Error<T>> {
+			let claim_digest = Self::claim_digest(algorithm, digest)?;
+
+			let proof = <DigestProofs<T>>::get(&claim_digest).ok_or(<Error<T>>::ClaimNotExist)?;
+			let delegated = proof.owner != *sender;
+			ensure!(
+				!delegated || Self::is_delegate(&proof.owner, sender),
+				<Error<T>>::NotClaimOwner
+			);
 
-			Ok(bounded_claim)
+			Ok((claim_digest, proof, delegated))
 		}
 	}
 }
@@ -0,0 +1,67 @@
+//! Binary Merkle trees over 32 byte leaves, as anchored by `anchor_batch`.
+//!
+//! Leaves and inner nodes are hashed under different prefixes, `0x00` and `0x01`, so a node
+//! can never be passed off as a leaf. A parent is the blake2-256 hash of its two children in
+//! ascending order, so a proof is only the list of siblings from the leaf up to the root. A
+//! node without a sibling is hashed with an all zero sibling, which no hash can be equal to.
+
+use sp_core::H256;
+use sp_std::prelude::*;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+fn hash_leaf(leaf: &H256) -> H256 {
+	let mut data = [0u8; 33];
+	data[0] = LEAF_PREFIX;
+	data[1..].copy_from_slice(leaf.as_bytes());
+	H256(sp_io::hashing::blake2_256(&data))
+}
+
+fn hash_pair(a: &H256, b: &H256) -> H256 {
+	let (low, high) = if a <= b { (a, b) } else { (b, a) };
+	let mut data = [0u8; 65];
+	data[0] = NODE_PREFIX;
+	data[1..33].copy_from_slice(low.as_bytes());
+	data[33..].copy_from_slice(high.as_bytes());
+	H256(sp_io::hashing::blake2_256(&data))
+}
+
+fn next_level(level: &[H256]) -> Vec<H256> {
+	level
+		.chunks(2)
+		.map(|pair| match pair {
+			[a, b] => hash_pair(a, b),
+			_ => hash_pair(&pair[0], &H256::zero()),
+		})
+		.collect()
+}
+
+/// Root of the tree over `leaves`, `None` if there are no leaves.
+pub fn root(leaves: &[H256]) -> Option<H256> {
+	let mut level: Vec<H256> = leaves.iter().map(hash_leaf).collect();
+	while level.len() > 1 {
+		level = next_level(&level);
+	}
+	level.first().copied()
+}
+
+/// Siblings proving that `leaves[index]` is part of the tree, `None` if out of bounds.
+pub fn proof(leaves: &[H256], mut index: usize) -> Option<Vec<H256>> {
+	if index >= leaves.len() {
+		return None
+	}
+	let mut proof = Vec::new();
+	let mut level: Vec<H256> = leaves.iter().map(hash_leaf).collect();
+	while level.len() > 1 {
+		proof.push(level.get(index ^ 1).copied().unwrap_or_else(H256::zero));
+		level = next_level(&level);
+		index /= 2;
+	}
+	Some(proof)
+}
+
+/// Whether `proof` leads from `leaf` to `root`.
+pub fn verify(leaf: H256, proof: &[H256], root: H256) -> bool {
+	proof.iter().fold(hash_leaf(&leaf), |node, sibling| hash_pair(&node, sibling)) == root
+}
@@ -0,0 +1,444 @@
+//! Storage migrations for pallet_poe.
+
+use super::*;
+use frame_support::{
+	log,
+	sp_runtime::traits::{Saturating, Zero},
+	traits::{Currency, Get, GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+// The migrations of one upgrade run all their `pre_upgrade` checks first and all their
+// `post_upgrade` checks last, so each only checks the claims when it has something to migrate,
+// under its own temporary key.
+
+/// Number of raw and digest claims, whatever the layout of their values.
+#[cfg(feature = "try-runtime")]
+fn claim_count<T: Config>() -> u32 {
+	(<Proofs<T>>::iter_keys().count() + <DigestProofs<T>>::iter_keys().count()) as u32
+}
+
+pub mod v1 {
+	use super::*;
+
+	/// The v0 value of `Proofs` and `DigestProofs`.
+	pub type OldProof<T> =
+		(<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber);
+
+	/// The v1 value of `Proofs` and `DigestProofs`.
+	#[derive(Encode, Decode)]
+	pub struct ProofInfoV1<AccountId, BlockNumber, Balance> {
+		pub owner: AccountId,
+		pub block_number: BlockNumber,
+		pub deposit: Balance,
+	}
+
+	pub type ProofInfoV1Of<T> = ProofInfoV1<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		BalanceOf<T>,
+	>;
+
+	#[frame_support::storage_alias]
+	type Proofs<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		BoundedVec<u8, <T as Config>::MaxclaimLength>,
+		ProofInfoV1Of<T>,
+	>;
+
+	#[frame_support::storage_alias]
+	type DigestProofs<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, ClaimDigest, ProofInfoV1Of<T>>;
+
+	/// Adds a deposit to every existing claim.
+	///
+	/// Owners are back-charged the per-byte deposit. Claims whose owner cannot afford it are
+	/// grandfathered with a zero deposit rather than dropped.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> MigrateToV1<T> {
+		fn charge(owner: &T::AccountId, len: usize) -> BalanceOf<T> {
+			let deposit = Pallet::<T>::deposit_for(len);
+			match T::Currency::reserve(owner, deposit) {
+				Ok(()) => deposit,
+				Err(_) => Zero::zero(),
+			}
+		}
+	}
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain >= 1 {
+				log::info!(target: "runtime::poe", "skipping v1 migration, on-chain version is {:?}", on_chain);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let (mut translated, mut grandfathered) = (0u64, 0u64);
+			<Proofs<T>>::translate::<OldProof<T>, _>(|claim, (owner, block_number)| {
+				translated += 1;
+				let deposit = Self::charge(&owner, claim.len());
+				if deposit.is_zero() {
+					grandfathered += 1;
+				}
+				Some(ProofInfoV1 { owner, block_number, deposit })
+			});
+			<DigestProofs<T>>::translate::<OldProof<T>, _>(|claim_digest, (owner, block_number)| {
+				translated += 1;
+				let deposit = Self::charge(&owner, claim_digest.digest.len());
+				if deposit.is_zero() {
+					grandfathered += 1;
+				}
+				Some(ProofInfoV1 { owner, block_number, deposit })
+			});
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(
+				target: "runtime::poe",
+				"migrated {} claims to v1, {} grandfathered without deposit",
+				translated,
+				grandfathered,
+			);
+			T::DbWeight::get().reads_writes(translated * 2 + 1, translated * 2 + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			if Pallet::<T>::on_chain_storage_version() < 1 {
+				Self::set_temp_storage(claim_count::<T>(), "poe_v1_claim_count");
+			}
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			let count: u32 = match Self::get_temp_storage("poe_v1_claim_count") {
+				Some(count) => count,
+				None => return Ok(()),
+			};
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 1,
+				"poe storage version must be at least 1"
+			);
+			frame_support::ensure!(claim_count::<T>() == count, "claims lost in v1 migration");
+			Ok(())
+		}
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// The v2 and v3 value of `Proofs` and `DigestProofs`.
+	#[derive(Encode, Decode)]
+	pub struct ProofInfoV2<AccountId, BlockNumber, Balance> {
+		pub owner: AccountId,
+		pub block_number: BlockNumber,
+		pub deposit: Balance,
+		pub expires_at: Option<BlockNumber>,
+	}
+
+	pub type ProofInfoV2Of<T> = ProofInfoV2<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		BalanceOf<T>,
+	>;
+
+	#[frame_support::storage_alias]
+	pub(super) type Proofs<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		BoundedVec<u8, <T as Config>::MaxclaimLength>,
+		ProofInfoV2Of<T>,
+	>;
+
+	#[frame_support::storage_alias]
+	pub(super) type DigestProofs<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, ClaimDigest, ProofInfoV2Of<T>>;
+
+	/// Adds an empty expiry to every existing claim and starts purging from the current block.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain != 1 {
+				log::info!(target: "runtime::poe", "skipping v2 migration, on-chain version is {:?}", on_chain);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			let upgrade = |old: v1::ProofInfoV1Of<T>| ProofInfoV2 {
+				owner: old.owner,
+				block_number: old.block_number,
+				deposit: old.deposit,
+				expires_at: None,
+			};
+			<Proofs<T>>::translate::<v1::ProofInfoV1Of<T>, _>(|_, old| {
+				translated += 1;
+				Some(upgrade(old))
+			});
+			<DigestProofs<T>>::translate::<v1::ProofInfoV1Of<T>, _>(|_, old| {
+				translated += 1;
+				Some(upgrade(old))
+			});
+			<ExpiryCursor<T>>::put(<frame_system::Pallet<T>>::block_number());
+
+			StorageVersion::new(2).put::<Pallet<T>>();
+			log::info!(target: "runtime::poe", "migrated {} claims to v2", translated);
+			T::DbWeight::get().reads_writes(translated + 2, translated + 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			if Pallet::<T>::on_chain_storage_version() < 2 {
+				Self::set_temp_storage(claim_count::<T>(), "poe_v2_claim_count");
+			}
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			let count: u32 = match Self::get_temp_storage("poe_v2_claim_count") {
+				Some(count) => count,
+				None => return Ok(()),
+			};
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 2,
+				"poe storage version must be at least 2"
+			);
+			frame_support::ensure!(claim_count::<T>() == count, "claims lost in v2 migration");
+			Ok(())
+		}
+	}
+}
+
+pub mod v3 {
+	use super::*;
+
+	/// Builds the `ClaimsByOwner` index from the existing claims.
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain != 2 {
+				log::info!(target: "runtime::poe", "skipping v3 migration, on-chain version is {:?}", on_chain);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut indexed = 0u64;
+			for (bounded_claim, proof) in <v2::Proofs<T>>::iter() {
+				indexed += 1;
+				<ClaimsByOwner<T>>::insert(&proof.owner, ClaimId::Raw(bounded_claim), ());
+			}
+			for (claim_digest, proof) in <v2::DigestProofs<T>>::iter() {
+				indexed += 1;
+				<ClaimsByOwner<T>>::insert(&proof.owner, ClaimId::Digest(claim_digest), ());
+			}
+
+			StorageVersion::new(3).put::<Pallet<T>>();
+			log::info!(target: "runtime::poe", "indexed {} claims by owner", indexed);
+			T::DbWeight::get().reads_writes(indexed + 1, indexed + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			if Pallet::<T>::on_chain_storage_version() < 3 {
+				Self::set_temp_storage(claim_count::<T>(), "poe_v3_claim_count");
+			}
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			let count: u32 = match Self::get_temp_storage("poe_v3_claim_count") {
+				Some(count) => count,
+				None => return Ok(()),
+			};
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 3,
+				"poe storage version must be at least 3"
+			);
+			frame_support::ensure!(claim_count::<T>() == count, "claims lost in v3 migration");
+			frame_support::ensure!(
+				<ClaimsByOwner<T>>::iter_keys().count() as u32 == count,
+				"claims missing from the owner index"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v4 {
+	use super::*;
+
+	/// Adds the timestamp and extrinsic index to every existing claim.
+	///
+	/// Neither is known for claims made before this upgrade, so they get a zero moment and no
+	/// extrinsic index.
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain != 3 {
+				log::info!(target: "runtime::poe", "skipping v4 migration, on-chain version is {:?}", on_chain);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			let upgrade = |old: v2::ProofInfoV2Of<T>| ProofInfo {
+				owner: old.owner,
+				block_number: old.block_number,
+				moment: Zero::zero(),
+				extrinsic_index: None,
+				deposit: old.deposit,
+				expires_at: old.expires_at,
+			};
+			<Proofs<T>>::translate::<v2::ProofInfoV2Of<T>, _>(|_, old| {
+				translated += 1;
+				Some(upgrade(old))
+			});
+			<DigestProofs<T>>::translate::<v2::ProofInfoV2Of<T>, _>(|_, old| {
+				translated += 1;
+				Some(upgrade(old))
+			});
+
+			StorageVersion::new(4).put::<Pallet<T>>();
+			log::info!(target: "runtime::poe", "migrated {} claims to v4", translated);
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			if Pallet::<T>::on_chain_storage_version() < 4 {
+				Self::set_temp_storage(claim_count::<T>(), "poe_v4_claim_count");
+			}
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			let count: u32 = match Self::get_temp_storage("poe_v4_claim_count") {
+				Some(count) => count,
+				None => return Ok(()),
+			};
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 4,
+				"poe storage version must be at least 4"
+			);
+			frame_support::ensure!(claim_count::<T>() == count, "claims lost in v4 migration");
+			// `ProofInfo` is still laid out as in v4.
+			let decoded =
+				<Proofs<T>>::iter_values().count() + <DigestProofs<T>>::iter_values().count();
+			frame_support::ensure!(decoded as u32 == count, "claims undecodable after v4 migration");
+			Ok(())
+		}
+	}
+}
+
+pub mod v5 {
+	use super::*;
+
+	/// Records the creation of every existing claim without a history and charges owners the
+	/// history deposit.
+	///
+	/// Claims whose owner cannot afford the history deposit keep their previous deposit.
+	pub struct MigrateToV5<T>(PhantomData<T>);
+
+	impl<T: Config> MigrateToV5<T> {
+		fn seed(claim_id: ClaimId<T>, proof: &mut ProofInfoOf<T>) -> bool {
+			if <ClaimHistory<T>>::contains_key(&claim_id) {
+				return false
+			}
+			let created = HistoryEntry {
+				change: ClaimChange::Created { owner: proof.owner.clone() },
+				block_number: proof.block_number,
+				moment: proof.moment,
+			};
+			let mut history = <BoundedVec<_, T::MaxHistoryLength>>::default();
+			if history.try_push(created).is_ok() {
+				<ClaimHistory<T>>::insert(&claim_id, history);
+			}
+			let deposit = T::HistoryDeposit::get();
+			if T::Currency::reserve(&proof.owner, deposit).is_ok() {
+				proof.deposit = proof.deposit.saturating_add(deposit);
+			}
+			true
+		}
+	}
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain != 4 {
+				log::info!(target: "runtime::poe", "skipping v5 migration, on-chain version is {:?}", on_chain);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let (mut claims, mut seeded) = (0u64, 0u64);
+			<Proofs<T>>::translate::<ProofInfoOf<T>, _>(|bounded_claim, mut proof| {
+				claims += 1;
+				if Self::seed(ClaimId::Raw(bounded_claim), &mut proof) {
+					seeded += 1;
+				}
+				Some(proof)
+			});
+			<DigestProofs<T>>::translate::<ProofInfoOf<T>, _>(|claim_digest, mut proof| {
+				claims += 1;
+				if Self::seed(ClaimId::Digest(claim_digest), &mut proof) {
+					seeded += 1;
+				}
+				Some(proof)
+			});
+
+			StorageVersion::new(5).put::<Pallet<T>>();
+			log::info!(target: "runtime::poe", "seeded the history of {} claims", seeded);
+			T::DbWeight::get().reads_writes(claims * 2 + seeded + 1, claims + seeded * 2 + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			if Pallet::<T>::on_chain_storage_version() < 5 {
+				Self::set_temp_storage(claim_count::<T>(), "poe_v5_claim_count");
+			}
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			let count: u32 = match Self::get_temp_storage("poe_v5_claim_count") {
+				Some(count) => count,
+				None => return Ok(()),
+			};
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 5,
+				"poe storage version must be at least 5"
+			);
+			frame_support::ensure!(claim_count::<T>() == count, "claims lost in v5 migration");
+			let without_history = <Proofs<T>>::iter_keys()
+				.map(ClaimId::Raw)
+				.chain(<DigestProofs<T>>::iter_keys().map(ClaimId::Digest))
+				.filter(|claim_id| !<ClaimHistory<T>>::contains_key(claim_id))
+				.count();
+			frame_support::ensure!(
+				without_history == 0,
+				"claims without history after v5 migration"
+			);
+			Ok(())
+		}
+	}
+}
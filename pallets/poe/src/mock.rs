@@ -1,4 +1,7 @@
-use frame_support::traits::{ConstU16, ConstU32, ConstU64};
+use frame_support::{
+	traits::{ConstU16, ConstU32, ConstU64},
+	PalletId,
+};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
@@ -20,6 +23,8 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		PoeModule: pallet_poe::{Pallet, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 	}
 );
 
@@ -41,7 +46,7 @@ impl system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -50,13 +55,61 @@ impl system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+frame_support::parameter_types! {
+	pub static AllowDirectTransfer: bool = true;
+	pub static AllowRecreateRevoked: bool = true;
+	pub static HistoryDeposit: u64 = 0;
+	pub const PoePalletId: PalletId = PalletId(*b"py/proof");
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
 impl pallet_poe::Config for Test {
 	type MaxclaimLength = ConstU32<512>;
 	type Event = Event;
 	type WeightInfo = ();
+	type Currency = Balances;
+	type ClaimDeposit = ConstU64<1>;
+	type MaxCosigners = ConstU32<3>;
+	type AllowDirectTransfer = AllowDirectTransfer;
+	type MaxBatchMetadata = ConstU32<16>;
+	type MaxMetadataLength = ConstU32<16>;
+	type MaxHistoryLength = ConstU32<4>;
+	type MaxReasonLength = ConstU32<16>;
+	type AllowRecreateRevoked = AllowRecreateRevoked;
+	type MaxPayloadLength = ConstU32<64>;
+	type MaxBatchSize = ConstU32<4>;
+	type DelegateDeposit = ConstU64<5>;
+	type HistoryDeposit = HistoryDeposit;
+	type OrgDeposit = ConstU64<10>;
+	type OrgMemberDeposit = ConstU64<3>;
+	type MaxOrgMembers = ConstU32<3>;
+	type PalletId = PoePalletId;
+	type TombstoneLifetime = ConstU64<10>;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 1_000), (2, 1_000), (3, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	t.into()
 }
@@ -0,0 +1,20 @@
+//! Offchain keys of claim payloads.
+//!
+//! `create_indexed_claim` keeps only the digest of a document in state and writes the
+//! document itself to the offchain database through offchain indexing, under [`key`]. Nodes
+//! started with `--enable-offchain-indexing true` can then serve it back.
+
+use codec::Encode;
+use sp_std::prelude::*;
+
+use crate::HashAlgorithm;
+
+/// Prefix of every payload key.
+pub const PAYLOAD_KEY_PREFIX: &[u8] = b"poe::payload::";
+
+/// Offchain key of the payload of the digest claim on `digest` under `algorithm`.
+pub fn key(algorithm: HashAlgorithm, digest: &[u8]) -> Vec<u8> {
+	let mut key = PAYLOAD_KEY_PREFIX.to_vec();
+	(algorithm, digest).encode_to(&mut key);
+	key
+}
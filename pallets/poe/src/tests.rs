@@ -1,31 +1,39 @@
 use super::*;
 use crate::mock::*;
-use frame_support::{assert_noop, assert_ok, BoundedVec};
+use frame_support::{assert_noop, assert_ok, weights::WithPostDispatchInfo, BoundedVec};
 
 #[test]
 fn create_claim_works() {
 	new_test_ext().execute_with(|| {
 		let claim = vec![0, 1];
 
-		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), None));
 
 		let bounded_claim =
 			<BoundedVec<u8, <Test as Config>::MaxclaimLength>>::try_from(claim.clone()).unwrap();
 
 		assert_eq!(
 			<Proofs<Test>>::get(&bounded_claim),
-			Some((1, <frame_system::Pallet<Test>>::block_number()))
+			Some(ProofInfo {
+				owner: 1,
+				block_number: <frame_system::Pallet<Test>>::block_number(),
+				moment: 0,
+				extrinsic_index: None,
+				deposit: 2,
+				expires_at: None,
+			})
 		);
+		assert_eq!(Balances::reserved_balance(1), 2);
 
 		assert_noop!(
-			PoeModule::create_claim(Origin::signed(1), claim.clone()),
+			PoeModule::create_claim(Origin::signed(1), claim.clone(), None),
 			<Error<Test>>::ProofAlreadyExist
 		);
 
 		let claim = vec![1; 513];
 
 		assert_noop!(
-			PoeModule::create_claim(Origin::signed(1), claim),
+			PoeModule::create_claim(Origin::signed(1), claim, None),
 			<Error<Test>>::ClaimTooLong
 		);
 	})
@@ -36,12 +44,16 @@ fn remove_claim() {
 	new_test_ext().execute_with(|| {
 		let claim = vec![1; 511];
 
-		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), None));
 
-		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(Balances::reserved_balance(1), 511);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone(), vec![]));
+
+		assert_eq!(Balances::reserved_balance(1), 0);
 
 		assert_noop!(
-			PoeModule::revoke_claim(Origin::signed(1), claim),
+			PoeModule::revoke_claim(Origin::signed(1), claim, vec![]),
 			<Error<Test>>::ClaimNotExist
 		);
 	})
@@ -52,7 +64,7 @@ fn transfer_claim() {
 	new_test_ext().execute_with(|| {
 		let claim = vec![1; 511];
 
-		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), None));
 
 		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
 
@@ -66,7 +78,1330 @@ fn transfer_claim() {
 
 		assert_eq!(
 			<Proofs<Test>>::get(&bounded_claim),
-			Some((2, <frame_system::Pallet<Test>>::block_number()))
+			Some(ProofInfo {
+				owner: 2,
+				block_number: <frame_system::Pallet<Test>>::block_number(),
+				moment: 0,
+				extrinsic_index: None,
+				deposit: 511,
+				expires_at: None,
+			})
+		);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 511);
+	})
+}
+
+#[test]
+fn digest_claim_works() {
+	new_test_ext().execute_with(|| {
+		let algorithm = HashAlgorithm::Sha2_256;
+		let digest = algorithm.hash(b"document");
+
+		assert_ok!(PoeModule::create_digest_claim(Origin::signed(1), algorithm, digest.clone()));
+
+		let claim_digest = PoeModule::claim_digest(algorithm, &digest).unwrap();
+
+		assert_eq!(
+			<DigestProofs<Test>>::get(&claim_digest).map(|proof| (proof.owner, proof.deposit)),
+			Some((1, 32))
+		);
+
+		assert_noop!(
+			PoeModule::create_digest_claim(Origin::signed(1), algorithm, digest.clone()),
+			<Error<Test>>::ProofAlreadyExist
+		);
+
+		// The same digest under another algorithm is a different claim.
+		assert_ok!(PoeModule::create_digest_claim(
+			Origin::signed(2),
+			HashAlgorithm::Keccak256,
+			digest.clone()
+		));
+
+		assert_noop!(
+			PoeModule::create_digest_claim(Origin::signed(1), algorithm, vec![0; 31]),
+			<Error<Test>>::InvalidDigestLength
+		);
+
+		assert_noop!(
+			PoeModule::transfer_digest_claim(Origin::signed(2), algorithm, digest.clone(), 3),
+			<Error<Test>>::NotClaimOwner
+		);
+
+		assert_ok!(PoeModule::transfer_digest_claim(Origin::signed(1), algorithm, digest.clone(), 2));
+
+		assert_eq!(
+			<DigestProofs<Test>>::get(&claim_digest).map(|proof| (proof.owner, proof.deposit)),
+			Some((2, 32))
+		);
+
+		assert_ok!(PoeModule::revoke_digest_claim(Origin::signed(2), algorithm, digest.clone(), vec![]));
+
+		assert_noop!(
+			PoeModule::revoke_digest_claim(Origin::signed(2), algorithm, digest, vec![]),
+			<Error<Test>>::ClaimNotExist
+		);
+	})
+}
+
+#[test]
+fn create_claim_requires_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(3), vec![1; 101], None),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(3), vec![1; 99], None));
+		assert_eq!(Balances::reserved_balance(3), 99);
+	})
+}
+
+#[test]
+fn migrations_charge_deposits() {
+	use frame_support::{
+		storage::{migration::put_storage_value, StorageHasher},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat,
+	};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<PoeModule>();
+
+		let old_proof = |claim: Vec<u8>, owner: u64| {
+			let bounded_claim =
+				<BoundedVec<u8, <Test as Config>::MaxclaimLength>>::try_from(claim).unwrap();
+			put_storage_value(
+				b"PoeModule",
+				b"Proofs",
+				&Blake2_128Concat::hash(&bounded_claim.encode()),
+				(owner, 1u64),
+			);
+			bounded_claim
+		};
+		let affordable = old_proof(vec![1; 10], 1);
+		// Account 3 holds 100 and cannot afford a 200 byte deposit.
+		let grandfathered = old_proof(vec![2; 200], 3);
+
+		crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(PoeModule::on_chain_storage_version(), 1);
+
+		crate::migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(PoeModule::on_chain_storage_version(), 2);
+
+		crate::migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		crate::migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(PoeModule::on_chain_storage_version(), 4);
+
+		// Account 3 cannot afford the history deposit either.
+		HistoryDeposit::set(150);
+		crate::migrations::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(PoeModule::on_chain_storage_version(), 5);
+		assert_eq!(
+			PoeModule::claim_history(vec![2; 200]),
+			vec![HistoryEntry {
+				change: ClaimChange::Created { owner: 3 },
+				block_number: 1,
+				moment: 0
+			}]
+		);
+
+		assert_eq!(
+			<Proofs<Test>>::get(&affordable),
+			Some(ProofInfo {
+				owner: 1,
+				block_number: 1,
+				moment: 0,
+				extrinsic_index: None,
+				deposit: 160,
+				expires_at: None,
+			})
+		);
+		assert_eq!(
+			<Proofs<Test>>::get(&grandfathered),
+			Some(ProofInfo {
+				owner: 3,
+				block_number: 1,
+				moment: 0,
+				extrinsic_index: None,
+				deposit: 0,
+				expires_at: None,
+			})
+		);
+		assert_eq!(Balances::reserved_balance(1), 160);
+		assert_eq!(Balances::reserved_balance(3), 0);
+
+		// Revoking a grandfathered claim releases nothing.
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(3), vec![2; 200], vec![]));
+		assert_eq!(Balances::free_balance(3), 100);
+	})
+}
+
+#[test]
+fn claim_expiry_and_renewal() {
+	use frame_support::traits::Hooks;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![7; 10];
+		let bounded_claim =
+			<BoundedVec<u8, <Test as Config>::MaxclaimLength>>::try_from(claim.clone()).unwrap();
+
+		assert_noop!(
+			PoeModule::create_claim_with_expiry(Origin::signed(1), claim.clone(), 1),
+			<Error<Test>>::InvalidExpiry
+		);
+
+		assert_ok!(PoeModule::create_claim_with_expiry(Origin::signed(1), claim.clone(), 5));
+		assert!(<Expiries<Test>>::contains_key(5, &bounded_claim));
+
+		assert_ok!(PoeModule::renew_claim(Origin::signed(1), claim.clone(), Some(10)));
+		assert!(!<Expiries<Test>>::contains_key(5, &bounded_claim));
+		assert!(<Expiries<Test>>::contains_key(10, &bounded_claim));
+
+		// Nothing is purged before the claim expires.
+		PoeModule::on_idle(9, Weight::MAX);
+		assert!(<Proofs<Test>>::contains_key(&bounded_claim));
+		assert_eq!(<ExpiryCursor<Test>>::get(), 10);
+
+		// An expired claim can no longer be moved, even before it is purged.
+		System::set_block_number(10);
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2),
+			<Error<Test>>::ClaimExpired
+		);
+
+		// Without enough weight the purge is postponed.
+		assert_eq!(PoeModule::on_idle(10, 0), 0);
+		assert!(<Proofs<Test>>::contains_key(&bounded_claim));
+
+		PoeModule::on_idle(10, Weight::MAX);
+		assert!(!<Proofs<Test>>::contains_key(&bounded_claim));
+		assert!(!<Expiries<Test>>::contains_key(10, &bounded_claim));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_last_event(Event::PoeModule(crate::Event::ClaimExpired(1, claim)));
+	})
+}
+
+#[test]
+fn cosigned_claim_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![3; 8];
+
+		assert_noop!(
+			PoeModule::propose_cosigned_claim(Origin::signed(1), claim.clone(), vec![2, 3], 3),
+			<Error<Test>>::InvalidThreshold
+		);
+		assert_noop!(
+			PoeModule::propose_cosigned_claim(Origin::signed(1), claim.clone(), vec![2, 2], 1),
+			<Error<Test>>::DuplicateCosigner
+		);
+		assert_noop!(
+			PoeModule::propose_cosigned_claim(Origin::signed(1), claim.clone(), vec![2, 3, 4, 5], 2),
+			<Error<Test>>::TooManyCosigners
+		);
+		assert_noop!(
+			PoeModule::propose_cosigned_claim(Origin::signed(1), claim.clone(), vec![2; 4], 1),
+			<Error<Test>>::TooManyCosigners
+		);
+
+		assert_ok!(PoeModule::propose_cosigned_claim(
+			Origin::signed(1),
+			claim.clone(),
+			vec![2, 3, 4],
+			2
+		));
+		assert_eq!(Balances::reserved_balance(1), 8);
+
+		// A co-signed claim blocks a single-owner claim on the same bytes.
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(2), claim.clone(), None),
+			<Error<Test>>::ProofAlreadyExist
+		);
+
+		assert_noop!(
+			PoeModule::endorse_claim(Origin::signed(5), claim.clone()),
+			<Error<Test>>::NotCosigner
 		);
+
+		assert_ok!(PoeModule::endorse_claim(Origin::signed(2), claim.clone()));
+		assert_noop!(
+			PoeModule::endorse_claim(Origin::signed(2), claim.clone()),
+			<Error<Test>>::AlreadyEndorsed
+		);
+		assert_eq!(
+			PoeModule::endorsement_status(claim.clone()),
+			Some(EndorsementInfo {
+				status: EndorsementStatus::Pending,
+				threshold: 2,
+				endorsed: vec![2],
+				pending: vec![3, 4],
+			})
+		);
+
+		assert_ok!(PoeModule::endorse_claim(Origin::signed(4), claim.clone()));
+		System::assert_last_event(Event::PoeModule(crate::Event::ClaimFinalized(claim.clone())));
+		assert_eq!(
+			PoeModule::endorsement_status(claim.clone()).map(|info| info.status),
+			Some(EndorsementStatus::Finalized)
+		);
+
+		// Once finalized, only the co-signers can revoke it, `threshold` of them.
+		assert_noop!(
+			PoeModule::revoke_cosigned_claim(Origin::signed(1), claim.clone(), vec![]),
+			<Error<Test>>::NotCosigner
+		);
+		assert_ok!(PoeModule::revoke_cosigned_claim(Origin::signed(2), claim.clone(), vec![]));
+		System::assert_last_event(Event::PoeModule(crate::Event::CosignedRevocationApproved(
+			2,
+			claim.clone(),
+		)));
+		assert_noop!(
+			PoeModule::revoke_cosigned_claim(Origin::signed(2), claim.clone(), vec![]),
+			<Error<Test>>::RevocationAlreadyApproved
+		);
+		assert_eq!(Balances::reserved_balance(1), 8);
+
+		assert_ok!(PoeModule::revoke_cosigned_claim(
+			Origin::signed(4),
+			claim.clone(),
+			b"void".to_vec()
+		));
+		System::assert_last_event(Event::PoeModule(crate::Event::CosignedClaimRevoked(
+			4,
+			claim.clone(),
+		)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(PoeModule::endorsement_status(claim.clone()), None);
+		let revocation = PoeModule::revocation(claim.clone()).unwrap();
+		assert_eq!((revocation.owner, revocation.revoked_by), (1, 4));
+		assert_eq!(revocation.reason, b"void".to_vec());
+		assert_eq!(
+			PoeModule::claim_history(claim.clone()).last().map(|entry| entry.change.clone()),
+			Some(ClaimChange::Revoked { owner: 1 })
+		);
+	})
+}
+
+#[test]
+fn pending_cosigned_claim_is_withdrawn_by_its_proposer() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![3; 4];
+
+		assert_ok!(PoeModule::propose_cosigned_claim(
+			Origin::signed(1),
+			claim.clone(),
+			vec![2, 3],
+			2
+		));
+		assert_ok!(PoeModule::endorse_claim(Origin::signed(2), claim.clone()));
+
+		assert_noop!(
+			PoeModule::revoke_cosigned_claim(Origin::signed(2), claim.clone(), vec![]),
+			<Error<Test>>::NotClaimOwner
+		);
+		assert_ok!(PoeModule::revoke_cosigned_claim(Origin::signed(1), claim.clone(), vec![]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(PoeModule::endorsement_status(claim.clone()), None);
+		assert!(PoeModule::revocation(claim).is_none());
+	})
+}
+
+#[test]
+fn two_step_transfer_works() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![4; 20];
+		let bounded_claim =
+			<BoundedVec<u8, <Test as Config>::MaxclaimLength>>::try_from(claim.clone()).unwrap();
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), None));
+
+		assert_noop!(
+			PoeModule::propose_transfer(Origin::signed(2), claim.clone(), 2),
+			<Error<Test>>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::accept_transfer(Origin::signed(2), claim.clone()),
+			<Error<Test>>::NoPendingTransfer
+		);
+
+		assert_ok!(PoeModule::propose_transfer(Origin::signed(1), claim.clone(), 3));
+		assert_ok!(PoeModule::cancel_transfer(Origin::signed(1), claim.clone()));
+		assert_noop!(
+			PoeModule::accept_transfer(Origin::signed(3), claim.clone()),
+			<Error<Test>>::NoPendingTransfer
+		);
+
+		assert_ok!(PoeModule::propose_transfer(Origin::signed(1), claim.clone(), 2));
+		assert_noop!(
+			PoeModule::accept_transfer(Origin::signed(3), claim.clone()),
+			<Error<Test>>::NotTransferRecipient
+		);
+		assert_ok!(PoeModule::accept_transfer(Origin::signed(2), claim.clone()));
+
+		assert_eq!(<Proofs<Test>>::get(&bounded_claim).map(|proof| proof.owner), Some(2));
+		assert!(!<PendingTransfers<Test>>::contains_key(&bounded_claim));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 20);
+	})
+}
+
+#[test]
+fn two_step_digest_transfer_works() {
+	new_test_ext().execute_with(|| {
+		let algorithm = HashAlgorithm::Blake2_256;
+		let digest = algorithm.hash(b"digest document");
+		let claim_digest = PoeModule::claim_digest(algorithm, &digest).unwrap();
+
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_digest_claim(Origin::signed(1), algorithm, digest.clone()));
+		let deposit = Balances::reserved_balance(1);
+
+		AllowDirectTransfer::set(false);
+		assert_noop!(
+			PoeModule::transfer_digest_claim(Origin::signed(1), algorithm, digest.clone(), 2),
+			<Error<Test>>::DirectTransferDisabled
+		);
+
+		assert_noop!(
+			PoeModule::propose_digest_transfer(Origin::signed(2), algorithm, digest.clone(), 2),
+			<Error<Test>>::NotClaimOwner
+		);
+		assert_ok!(PoeModule::propose_digest_transfer(
+			Origin::signed(1),
+			algorithm,
+			digest.clone(),
+			3
+		));
+		assert_ok!(PoeModule::cancel_digest_transfer(Origin::signed(1), algorithm, digest.clone()));
+		assert_noop!(
+			PoeModule::accept_digest_transfer(Origin::signed(3), algorithm, digest.clone()),
+			<Error<Test>>::NoPendingTransfer
+		);
+
+		assert_ok!(PoeModule::propose_digest_transfer(
+			Origin::signed(1),
+			algorithm,
+			digest.clone(),
+			2
+		));
+		assert_noop!(
+			PoeModule::accept_digest_transfer(Origin::signed(3), algorithm, digest.clone()),
+			<Error<Test>>::NotTransferRecipient
+		);
+		assert_ok!(PoeModule::accept_digest_transfer(Origin::signed(2), algorithm, digest.clone()));
+		System::assert_last_event(Event::PoeModule(crate::Event::DigestClaimTransfer(
+			1,
+			algorithm,
+			digest,
+			2,
+		)));
+
+		assert_eq!(<DigestProofs<Test>>::get(&claim_digest).map(|proof| proof.owner), Some(2));
+		assert!(!<PendingDigestTransfers<Test>>::contains_key(&claim_digest));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), deposit);
+	})
+}
+
+#[test]
+fn direct_transfer_can_be_disabled() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![5; 4];
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_ok!(PoeModule::propose_transfer(Origin::signed(1), claim.clone(), 3));
+
+		AllowDirectTransfer::set(false);
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2),
+			<Error<Test>>::DirectTransferDisabled
+		);
+
+		AllowDirectTransfer::set(true);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
+
+		// The direct transfer dropped the stale offer.
+		assert_noop!(
+			PoeModule::accept_transfer(Origin::signed(3), claim),
+			<Error<Test>>::NoPendingTransfer
+		);
+	})
+}
+
+#[test]
+fn merkle_proofs_verify() {
+	let leaves: Vec<H256> = (0u8..5).map(H256::repeat_byte).collect();
+	let root = merkle::root(&leaves).unwrap();
+
+	for (index, leaf) in leaves.iter().enumerate() {
+		let proof = merkle::proof(&leaves, index).unwrap();
+		assert!(merkle::verify(*leaf, &proof, root));
+		assert!(!merkle::verify(H256::repeat_byte(9), &proof, root));
+	}
+
+	// A single leaf is still hashed, so a root is never a leaf of another tree.
+	assert_ne!(merkle::root(&leaves[..1]), Some(leaves[0]));
+	assert!(merkle::verify(leaves[0], &[], merkle::root(&leaves[..1]).unwrap()));
+	assert_eq!(merkle::root(&[]), None);
+	assert_eq!(merkle::proof(&leaves, 5), None);
+
+	// An inner node does not pass as a leaf of the same tree.
+	let proof = merkle::proof(&leaves, 0).unwrap();
+	let inner = merkle::root(&leaves[..2]).unwrap();
+	assert!(!merkle::verify(inner, &proof[1..], root));
+
+	// The odd node is hashed rather than promoted, so appending a copy of it changes the root.
+	let mut padded = leaves.clone();
+	padded.push(leaves[4]);
+	assert_ne!(merkle::root(&padded), Some(root));
+}
+
+#[test]
+fn anchor_batch_works() {
+	new_test_ext().execute_with(|| {
+		let leaves: Vec<H256> = (0u8..3).map(H256::repeat_byte).collect();
+		let root = merkle::root(&leaves).unwrap();
+		let proof = merkle::proof(&leaves, 1).unwrap();
+
+		assert!(!PoeModule::verify_inclusion(leaves[1], proof.clone(), root));
+
+		assert_noop!(
+			PoeModule::anchor_batch(Origin::signed(1), root, 0, vec![]),
+			<Error<Test>>::EmptyBatch
+		);
+		assert_noop!(
+			PoeModule::anchor_batch(Origin::signed(1), root, 3, vec![0; 17]),
+			<Error<Test>>::MetadataTooLong
+		);
+
+		assert_ok!(PoeModule::anchor_batch(Origin::signed(1), root, 3, b"invoices".to_vec()));
+		assert_eq!(Balances::reserved_balance(1), 40);
+		assert_noop!(
+			PoeModule::anchor_batch(Origin::signed(2), root, 3, vec![]),
+			<Error<Test>>::BatchAlreadyAnchored
+		);
+
+		assert!(PoeModule::verify_inclusion(leaves[1], proof.clone(), root));
+		assert!(!PoeModule::verify_inclusion(leaves[0], proof.clone(), root));
+
+		assert_noop!(PoeModule::remove_batch(Origin::signed(2), root), <Error<Test>>::NotClaimOwner);
+		assert_noop!(
+			PoeModule::remove_batch(Origin::signed(1), H256::zero()),
+			<Error<Test>>::BatchNotExist
+		);
+		assert_ok!(PoeModule::remove_batch(Origin::signed(1), root));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(!PoeModule::verify_inclusion(leaves[1], proof, root));
+	})
+}
+
+#[test]
+fn claims_are_indexed_by_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![6; 12];
+		let digest = HashAlgorithm::Keccak256.hash(b"contract");
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_ok!(PoeModule::create_digest_claim(
+			Origin::signed(1),
+			HashAlgorithm::Keccak256,
+			digest.clone()
+		));
+
+		let mut owned: Vec<_> =
+			PoeModule::claims_by_owner(1).into_iter().map(|record| record.claim).collect();
+		owned.sort();
+		let mut expected = vec![claim.clone(), digest.clone()];
+		expected.sort();
+		assert_eq!(owned, expected);
+
+		assert_eq!(
+			PoeModule::claim_record(digest.clone()),
+			Some(ClaimRecord {
+				claim: digest.clone(),
+				algorithm: Some(HashAlgorithm::Keccak256),
+				owner: 1,
+				block_number: 0,
+				moment: 0,
+				expires_at: None,
+				org: None,
+				submitter: None,
+			})
+		);
+		assert_eq!(PoeModule::claim_record(claim.clone()).map(|record| record.algorithm), Some(None));
+		assert_eq!(PoeModule::claim_record(vec![9; 32]), None);
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
+		assert_ok!(PoeModule::revoke_digest_claim(
+			Origin::signed(1),
+			HashAlgorithm::Keccak256,
+			digest,
+			vec![]
+		));
+
+		assert!(PoeModule::claims_by_owner(1).is_empty());
+		assert_eq!(
+			PoeModule::claims_by_owner(2).into_iter().map(|record| record.claim).collect::<Vec<_>>(),
+			vec![claim]
+		);
+	})
+}
+
+#[test]
+fn claim_metadata_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![8; 10];
+		let bounded_claim =
+			<BoundedVec<u8, <Test as Config>::MaxclaimLength>>::try_from(claim.clone()).unwrap();
+		let metadata = ClaimMetadata {
+			title: b"Lease".to_vec(),
+			mime_type: b"application/pdf".to_vec(),
+			uri: b"ipfs://lease".to_vec(),
+		};
+
+		assert_noop!(
+			PoeModule::create_claim(
+				Origin::signed(1),
+				claim.clone(),
+				Some(ClaimMetadata { title: vec![0; 17], ..Default::default() })
+			),
+			<Error<Test>>::MetadataTooLong
+		);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), Some(metadata.clone())));
+		System::assert_last_event(Event::PoeModule(crate::Event::ClaimCreated(
+			1,
+			claim.clone(),
+			Some(metadata.clone()),
+		)));
+		// 10 claim bytes and 32 metadata bytes.
+		assert_eq!(Balances::reserved_balance(1), 42);
+		assert_eq!(
+			<ClaimMetadataOf<Test>>::get(&bounded_claim).map(|metadata| metadata.title.into_inner()),
+			Some(b"Lease".to_vec())
+		);
+
+		assert_noop!(
+			PoeModule::set_claim_metadata(Origin::signed(2), claim.clone(), None),
+			<Error<Test>>::NotClaimOwner
+		);
+
+		let shorter = ClaimMetadata { title: b"Lease".to_vec(), ..Default::default() };
+		assert_ok!(PoeModule::set_claim_metadata(Origin::signed(1), claim.clone(), Some(shorter)));
+		assert_eq!(Balances::reserved_balance(1), 15);
+
+		assert_ok!(PoeModule::set_claim_metadata(Origin::signed(1), claim.clone(), None));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert!(!<ClaimMetadataOf<Test>>::contains_key(&bounded_claim));
+
+		assert_ok!(PoeModule::set_claim_metadata(Origin::signed(1), claim.clone(), Some(metadata)));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim, vec![]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(!<ClaimMetadataOf<Test>>::contains_key(&bounded_claim));
+	})
+}
+
+#[test]
+fn claim_history_is_kept() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![9; 4];
+		let entry = |change, block_number, moment| HistoryEntry { change, block_number, moment };
+
+		System::set_block_number(1);
+		Timestamp::set_timestamp(6_000);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), None));
+
+		System::set_block_number(2);
+		Timestamp::set_timestamp(12_000);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
+
+		System::set_block_number(3);
+		Timestamp::set_timestamp(18_000);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(2), claim.clone(), 1));
+
+		System::set_block_number(4);
+		Timestamp::set_timestamp(24_000);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
+
+		// A full history drops its oldest entry to record the revocation.
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), claim.clone(), vec![]));
+
+		assert_eq!(
+			PoeModule::claim_history(claim.clone()),
+			vec![
+				entry(ClaimChange::Transferred { from: 1, to: 2 }, 2, 12_000),
+				entry(ClaimChange::Transferred { from: 2, to: 1 }, 3, 18_000),
+				entry(ClaimChange::Transferred { from: 1, to: 2 }, 4, 24_000),
+				entry(ClaimChange::Revoked { owner: 2 }, 4, 24_000),
+			]
+		);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), None));
+		assert_eq!(
+			PoeModule::claim_history(claim).last(),
+			Some(&entry(ClaimChange::Created { owner: 1 }, 4, 24_000))
+		);
+
+		let digest = HashAlgorithm::Sha2_256.hash(b"contract");
+		assert_ok!(PoeModule::create_digest_claim(
+			Origin::signed(1),
+			HashAlgorithm::Sha2_256,
+			digest.clone()
+		));
+		assert_ok!(PoeModule::revoke_digest_claim(
+			Origin::signed(1),
+			HashAlgorithm::Sha2_256,
+			digest.clone(),
+			vec![]
+		));
+		assert_eq!(
+			PoeModule::claim_history(digest),
+			vec![
+				entry(ClaimChange::Created { owner: 1 }, 4, 24_000),
+				entry(ClaimChange::Revoked { owner: 1 }, 4, 24_000),
+			]
+		);
+		assert!(PoeModule::claim_history(vec![0; 4]).is_empty());
+	})
+}
+
+#[test]
+fn history_deposit_is_reserved_with_claims() {
+	new_test_ext().execute_with(|| {
+		HistoryDeposit::set(20);
+		let digest = HashAlgorithm::Sha2_256.hash(b"contract");
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![9; 4], None));
+		assert_ok!(PoeModule::create_digest_claim(
+			Origin::signed(1),
+			HashAlgorithm::Sha2_256,
+			digest.clone()
+		));
+		assert_eq!(Balances::reserved_balance(1), 4 + 32 + 2 * 20);
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), vec![9; 4], 2));
+		assert_eq!(Balances::reserved_balance(2), 4 + 20);
+
+		// Account 3 holds 100, enough for the claim but not with its history.
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(3), vec![1; 90], None),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	})
+}
+
+#[test]
+fn org_claims_are_managed_by_admins() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![5; 8];
+
+		assert_ok!(PoeModule::create_org(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_noop!(
+			PoeModule::set_org_member(Origin::signed(2), 0, 2, OrgRole::Member),
+			<Error<Test>>::NotOrgAdmin
+		);
+		assert_ok!(PoeModule::set_org_member(Origin::signed(1), 0, 2, OrgRole::Member));
+		// The admin adding a member pays its deposit, changing its role costs nothing.
+		assert_eq!(Balances::reserved_balance(1), 13);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_noop!(
+			PoeModule::create_org_claim(Origin::signed(3), 0, claim.clone(), None),
+			<Error<Test>>::NotOrgMember
+		);
+		assert_noop!(
+			PoeModule::create_org_claim(Origin::signed(2), 1, claim.clone(), None),
+			<Error<Test>>::OrgNotExist
+		);
+
+		assert_ok!(PoeModule::create_org_claim(Origin::signed(2), 0, claim.clone(), None));
+		// The member pays the deposit, but the claim belongs to the organization.
+		let org_account = PoeModule::org_account(0);
+		let record = PoeModule::claim_record(claim.clone()).unwrap();
+		assert_eq!((record.owner, record.org, record.submitter), (org_account, Some(0), Some(2)));
+		assert_eq!(Balances::reserved_balance(2), 8);
+		assert!(PoeModule::claims_by_owner(2).is_empty());
+		assert_eq!(PoeModule::claims_by_owner(org_account).len(), 1);
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(2), claim.clone(), vec![]),
+			<Error<Test>>::NotClaimOwner
+		);
+
+		assert_noop!(
+			PoeModule::remove_org_member(Origin::signed(1), 0, 1),
+			<Error<Test>>::LastOrgAdmin
+		);
+		assert_noop!(
+			PoeModule::set_org_member(Origin::signed(1), 0, 1, OrgRole::Member),
+			<Error<Test>>::LastOrgAdmin
+		);
+		assert_ok!(PoeModule::set_org_member(Origin::signed(1), 0, 3, OrgRole::Admin));
+		assert_eq!(Balances::reserved_balance(1), 16);
+		assert_noop!(
+			PoeModule::set_org_member(Origin::signed(1), 0, 4, OrgRole::Member),
+			<Error<Test>>::TooManyOrgMembers
+		);
+		assert_ok!(PoeModule::remove_org_member(Origin::signed(3), 0, 1));
+		assert_ok!(PoeModule::remove_org_member(Origin::signed(3), 0, 2));
+		assert_eq!(Orgs::<Test>::get(0).map(|info| (info.admins, info.members)), Some((1, 1)));
+		// The deposit of a removed member goes back to the admin that added it.
+		assert_eq!(Balances::reserved_balance(1), 13);
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(1), claim.clone(), vec![]),
+			<Error<Test>>::NotClaimOwner
+		);
+
+		// An admin pays for the metadata it adds, the deposit stays with the claim.
+		let metadata = ClaimMetadata { title: b"Deed".to_vec(), ..Default::default() };
+		assert_ok!(PoeModule::set_claim_metadata(Origin::signed(3), claim.clone(), Some(metadata)));
+		assert_eq!(Balances::free_balance(3), 96);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::reserved_balance(2), 12);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(3), claim.clone(), vec![]));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(PoeModule::revocation(claim.clone()).map(|info| info.owner), Some(org_account));
+		assert!(PoeModule::claims_by_owner(org_account).is_empty());
+		assert!(!OrgClaims::<Test>::contains_key(
+			<BoundedVec<u8, <Test as Config>::MaxclaimLength>>::try_from(claim).unwrap()
+		));
+	})
+}
+
+#[test]
+fn revoked_claims_leave_a_tombstone() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		let claim = vec![7; 6];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), None));
+
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(1), claim.clone(), vec![0; 17]),
+			<Error<Test>>::ReasonTooLong
+		);
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone(), b"superseded".to_vec()));
+		System::assert_last_event(Event::PoeModule(crate::Event::ClaimRevoked(
+			1,
+			claim.clone(),
+			b"superseded".to_vec(),
+		)));
+		assert_eq!(
+			PoeModule::revocation(claim.clone()),
+			Some(RevocationInfo {
+				owner: 1,
+				revoked_by: 1,
+				block_number: 3,
+				reason: b"superseded".to_vec(),
+			})
+		);
+		assert_eq!(PoeModule::revocation(vec![8; 6]), None);
+
+		AllowRecreateRevoked::set(false);
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(2), claim.clone(), None),
+			<Error<Test>>::ClaimWasRevoked
+		);
+
+		AllowRecreateRevoked::set(true);
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), claim.clone(), None));
+		assert_eq!(PoeModule::revocation(claim), None);
+
+		let algorithm = HashAlgorithm::Blake2_256;
+		let digest = algorithm.hash(b"report");
+		assert_ok!(PoeModule::create_digest_claim(Origin::signed(1), algorithm, digest.clone()));
+		assert_ok!(PoeModule::revoke_digest_claim(
+			Origin::signed(1),
+			algorithm,
+			digest.clone(),
+			b"leaked".to_vec()
+		));
+		assert_eq!(
+			PoeModule::revocation(digest).map(|info| info.reason),
+			Some(b"leaked".to_vec())
+		);
+	})
+}
+
+#[test]
+fn tombstones_keep_a_deposit_until_purged() {
+	new_test_ext().execute_with(|| {
+		HistoryDeposit::set(20);
+		System::set_block_number(3);
+		let claim_id = |claim: &[u8]| {
+			ClaimId::<Test>::Raw(BoundedVec::try_from(claim.to_vec()).unwrap())
+		};
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![7; 6], None));
+		assert_noop!(
+			PoeModule::purge_claim_history(Origin::signed(2), claim_id(&[7; 6])),
+			<Error<Test>>::ClaimNotEnded
+		);
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![7; 6], vec![]));
+		// The history deposit stays reserved with the tombstone.
+		assert_eq!(Balances::reserved_balance(1), 20);
+		assert_noop!(
+			PoeModule::purge_claim_history(Origin::signed(2), claim_id(&[7; 6])),
+			<Error<Test>>::TombstoneTooRecent
+		);
+
+		System::set_block_number(13);
+		assert_ok!(PoeModule::purge_claim_history(Origin::signed(2), claim_id(&[7; 6])));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(PoeModule::claim_history(vec![7; 6]).is_empty());
+		assert_noop!(
+			PoeModule::purge_claim_history(Origin::signed(2), claim_id(&[7; 6])),
+			<Error<Test>>::ClaimNotEnded
+		);
+
+		// The tombstone outlives the history, so the claim still reads as revoked.
+		assert_eq!(PoeModule::revocation(vec![7; 6]).map(|info| info.owner), Some(1));
+		AllowRecreateRevoked::set(false);
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(2), vec![7; 6], None),
+			<Error<Test>>::ClaimWasRevoked
+		);
+		AllowRecreateRevoked::set(true);
+
+		// Creating the claim again returns the deposit kept for its history.
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![8; 6], None));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![8; 6], vec![]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![8; 6], None));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 26);
+
+		// Expired claims keep the history deposit too.
+		assert_ok!(PoeModule::create_claim_with_expiry(Origin::signed(1), vec![9; 6], 15));
+		System::set_block_number(15);
+		PoeModule::expire(15, BoundedVec::try_from(vec![9; 6]).unwrap());
+		assert_eq!(Balances::reserved_balance(1), 20);
+		System::set_block_number(25);
+		assert_ok!(PoeModule::purge_claim_history(Origin::signed(3), claim_id(&[9; 6])));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	})
+}
+
+#[test]
+fn credentials_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let credential = H256::repeat_byte(7);
+		let claim = credential.as_bytes().to_vec();
+		let schema = H256::repeat_byte(9);
+
+		assert_noop!(
+			PoeModule::issue_credential(Origin::signed(1), credential, 2, schema, None),
+			<Error<Test>>::NotTrustedIssuer
+		);
+		assert_noop!(
+			PoeModule::add_issuer(Origin::signed(1), 1),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(PoeModule::add_issuer(Origin::root(), 1));
+		assert_ok!(PoeModule::issue_credential(Origin::signed(1), credential, 2, schema, Some(10)));
+		// The credential is a claim of the issuer.
+		assert_eq!(PoeModule::claim_record(claim.clone()).map(|record| record.owner), Some(1));
+		assert_eq!(Balances::reserved_balance(1), 32);
+		assert_noop!(
+			PoeModule::issue_credential(Origin::signed(1), credential, 2, schema, None),
+			<Error<Test>>::ProofAlreadyExist
+		);
+
+		let status = PoeModule::credential_status(credential).unwrap();
+		assert_eq!((status.issuer, status.subject, status.schema), (1, 2, schema));
+		assert_eq!(status.expires_at, Some(10));
+		assert!(status.valid);
+		assert_eq!(PoeModule::credential_status(H256::repeat_byte(8)), None);
+
+		// Credentials stop being valid once their issuer is no longer trusted or they expire.
+		assert_ok!(PoeModule::remove_issuer(Origin::root(), 1));
+		assert!(!PoeModule::credential_status(credential).unwrap().valid);
+		assert_ok!(PoeModule::add_issuer(Origin::root(), 1));
+		assert!(PoeModule::credential_status(credential).unwrap().valid);
+		System::set_block_number(10);
+		assert!(!PoeModule::credential_status(credential).unwrap().valid);
+		System::set_block_number(1);
+
+		// The claim stays with the issuer.
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 3),
+			<Error<Test>>::CredentialNotTransferable
+		);
+		assert_noop!(
+			PoeModule::propose_transfer(Origin::signed(1), claim.clone(), 3),
+			<Error<Test>>::CredentialNotTransferable
+		);
+
+		assert_noop!(
+			PoeModule::revoke_credential(Origin::signed(3), credential, vec![]),
+			<Error<Test>>::NotCredentialParty
+		);
+		assert_ok!(PoeModule::revoke_credential(Origin::signed(2), credential, b"lost".to_vec()));
+		System::assert_last_event(Event::PoeModule(crate::Event::CredentialRevoked(
+			2,
+			1,
+			credential,
+			b"lost".to_vec(),
+		)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(PoeModule::claim_record(claim), None);
+
+		let status = PoeModule::credential_status(credential).unwrap();
+		assert!(!status.valid);
+		assert_eq!(status.subject, 2);
+		assert_eq!(
+			status.revocation.map(|revocation| (revocation.owner, revocation.revoked_by)),
+			Some((1, 2))
+		);
+		assert_noop!(
+			PoeModule::revoke_credential(Origin::signed(1), credential, vec![]),
+			<Error<Test>>::CredentialNotExist
+		);
+	})
+}
+
+#[test]
+fn credentials_are_revoked_through_the_claim_owner_check() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let credential = H256::repeat_byte(7);
+		assert_ok!(PoeModule::add_issuer(Origin::root(), 1));
+		assert_ok!(PoeModule::issue_credential(Origin::signed(1), credential, 2, H256::zero(), None));
+
+		assert_ok!(PoeModule::add_delegate(Origin::signed(1), 3, None));
+		assert_ok!(PoeModule::revoke_credential(Origin::signed(3), credential, vec![]));
+		System::assert_last_event(Event::PoeModule(crate::Event::DelegateActed(
+			3,
+			1,
+			credential.as_bytes().to_vec(),
+		)));
+		assert_eq!(
+			PoeModule::credential_status(credential)
+				.and_then(|status| status.revocation)
+				.map(|revocation| revocation.revoked_by),
+			Some(3)
+		);
+	})
+}
+
+#[test]
+fn indexed_claims_keep_payloads_offchain() {
+	let mut ext = new_test_ext();
+	let algorithm = HashAlgorithm::Sha2_256;
+	let payload = b"full text of the contract".to_vec();
+	let digest = algorithm.hash(&payload);
+
+	ext.execute_with(|| {
+		assert_noop!(
+			PoeModule::create_indexed_claim(Origin::signed(1), algorithm, vec![0; 65]),
+			<Error<Test>>::PayloadTooLong
+		);
+		assert_ok!(PoeModule::create_indexed_claim(Origin::signed(1), algorithm, payload.clone()));
+		// Only the digest is kept in state, and paid for.
+		assert_eq!(PoeModule::claim_record(digest.clone()).map(|record| record.owner), Some(1));
+		assert_eq!(Balances::reserved_balance(1), 32);
+	});
+	ext.persist_offchain_overlay();
+	assert_eq!(ext.offchain_db().get(&crate::payload::key(algorithm, &digest)), Some(payload));
+
+	ext.execute_with(|| {
+		assert_ok!(PoeModule::revoke_digest_claim(
+			Origin::signed(1),
+			algorithm,
+			digest.clone(),
+			vec![]
+		));
+	});
+	ext.persist_offchain_overlay();
+	assert_eq!(ext.offchain_db().get(&crate::payload::key(algorithm, &digest)), None);
+}
+
+#[test]
+fn proofs_record_time_and_extrinsic() {
+	use frame_support::storage::unhashed;
+	use sp_core::storage::well_known_keys::EXTRINSIC_INDEX;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Timestamp::set_timestamp(12_000);
+		unhashed::put(EXTRINSIC_INDEX, &3u32);
+
+		let claim = vec![1; 10];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), None));
+
+		let bounded_claim =
+			<BoundedVec<u8, <Test as Config>::MaxclaimLength>>::try_from(claim.clone()).unwrap();
+		let proof = <Proofs<Test>>::get(&bounded_claim).unwrap();
+		assert_eq!((proof.block_number, proof.moment, proof.extrinsic_index), (2, 12_000, Some(3)));
+		assert_eq!(PoeModule::claim_record(claim.clone()).map(|record| record.moment), Some(12_000));
+		assert_eq!(
+			PoeModule::proof_anchor(claim.clone()),
+			Some(ProofAnchor {
+				key: <Proofs<Test>>::hashed_key_for(&bounded_claim),
+				block_number: 2,
+				moment: 12_000,
+				extrinsic_index: Some(3),
+			})
+		);
+
+		// A transfer moves the anchor to the transferring extrinsic.
+		System::set_block_number(3);
+		Timestamp::set_timestamp(18_000);
+		unhashed::put(EXTRINSIC_INDEX, &1u32);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
+		let anchor = PoeModule::proof_anchor(claim).unwrap();
+		assert_eq!((anchor.block_number, anchor.moment, anchor.extrinsic_index), (3, 18_000, Some(1)));
+
+		assert_eq!(PoeModule::proof_anchor(vec![9; 10]), None);
+	})
+}
+
+// Only guards that weights.rs covers every benchmark with the same components; the numbers
+// themselves must come from `scripts/benchmark.sh`.
+#[cfg(feature = "runtime-benchmarks")]
+#[test]
+fn weights_match_benchmarks() {
+	use frame_benchmarking::Benchmarking;
+
+	// `name(components)` of every function in the impl starting at `header`.
+	let source = include_str!("weights.rs");
+	let weight_fns = |header: &str| {
+		let body = source.split(header).nth(1).expect("weights.rs implements WeightInfo");
+		let mut fns: Vec<String> = body
+			.split("\nimpl")
+			.next()
+			.unwrap()
+			.lines()
+			.filter_map(|line| line.trim_start().strip_prefix("fn "))
+			.map(|signature| {
+				let (name, rest) = signature.split_once('(').unwrap();
+				let components: Vec<&str> = rest
+					.split(')')
+					.next()
+					.unwrap()
+					.split(',')
+					.filter_map(|param| param.split(':').next())
+					.map(|param| param.trim().trim_start_matches('_'))
+					.filter(|param| !param.is_empty())
+					.collect();
+				format!("{}({})", name, components.join(", "))
+			})
+			.collect();
+		fns.sort();
+		fns
+	};
+
+	let mut benchmarks: Vec<String> = PoeModule::benchmarks(false)
+		.into_iter()
+		.map(|benchmark| {
+			let components: Vec<String> = benchmark
+				.components
+				.iter()
+				.map(|(param, _, _)| format!("{:?}", param))
+				.collect();
+			format!("{}({})", String::from_utf8(benchmark.name).unwrap(), components.join(", "))
+		})
+		.collect();
+	benchmarks.sort();
+
+	assert_eq!(weight_fns("WeightInfo for SubstrateWeight<T> {"), benchmarks);
+	assert_eq!(weight_fns("WeightInfo for () {"), benchmarks);
+}
+
+#[test]
+fn genesis_claims_are_created() {
+	use frame_support::traits::GenesisBuild;
+
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 1_000), (3, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let digest = HashAlgorithm::Sha2_256.hash(b"document");
+	crate::GenesisConfig::<Test> {
+		claims: vec![
+			(GenesisClaim::Raw(vec![1; 10]), 1, 5),
+			(GenesisClaim::Digest(HashAlgorithm::Sha2_256, digest.clone()), 3, 7),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		let record = PoeModule::claim_record(vec![1; 10]).unwrap();
+		assert_eq!((record.owner, record.block_number, record.moment), (1, 5, 0));
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		let record = PoeModule::claim_record(digest.clone()).unwrap();
+		assert_eq!(
+			(record.owner, record.block_number, record.algorithm),
+			(3, 7, Some(HashAlgorithm::Sha2_256))
+		);
+		assert_eq!(Balances::reserved_balance(3), 32);
+		assert_eq!(PoeModule::claims_by_owner(3).len(), 1);
+		assert_eq!(PoeModule::claim_history(digest).len(), 1);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![1; 10], vec![]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+#[should_panic(expected = "genesis claim owner cannot afford the deposit")]
+fn genesis_claims_need_a_deposit() {
+	use frame_support::traits::GenesisBuild;
+
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(3, 1)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	crate::GenesisConfig::<Test> { claims: vec![(GenesisClaim::Raw(vec![1; 10]), 3, 5)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+}
+
+#[test]
+fn batch_claims_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![2], None));
+
+		// A claim that already exists fails the whole atomic batch, which is only charged for
+		// the claims it got to.
+		assert_noop!(
+			PoeModule::create_claims(
+				Origin::signed(1),
+				vec![vec![1], vec![2], vec![3]],
+				BatchMode::Atomic
+			),
+			<Error<Test>>::ProofAlreadyExist.with_weight(<() as WeightInfo>::create_claims(2))
+		);
+		assert_noop!(
+			PoeModule::create_claims(Origin::signed(1), vec![vec![9]; 5], BatchMode::Atomic),
+			<Error<Test>>::BatchTooLarge.with_weight(<() as WeightInfo>::create_claims(0))
+		);
+
+		// A best effort batch skips it and creates the others.
+		assert_ok!(PoeModule::create_claims(
+			Origin::signed(1),
+			vec![vec![1], vec![2], vec![3]],
+			BatchMode::BestEffort
+		));
+		System::assert_has_event(Event::PoeModule(crate::Event::ClaimBatchItem(1, 0, Ok(()))));
+		System::assert_has_event(Event::PoeModule(crate::Event::ClaimBatchItem(
+			1,
+			1,
+			Err(<Error<Test>>::ProofAlreadyExist.into()),
+		)));
+		System::assert_last_event(Event::PoeModule(crate::Event::ClaimBatchItem(1, 2, Ok(()))));
+		assert_eq!(PoeModule::claims_by_owner(1).len(), 2);
+		assert_eq!(Balances::reserved_balance(1), 2);
+
+		assert_noop!(
+			PoeModule::revoke_claims(
+				Origin::signed(1),
+				vec![vec![1], vec![2], vec![3]],
+				vec![],
+				BatchMode::Atomic
+			),
+			<Error<Test>>::NotClaimOwner.with_weight(<() as WeightInfo>::revoke_claims(2, 0))
+		);
+		assert_ok!(PoeModule::revoke_claims(
+			Origin::signed(1),
+			vec![vec![1], vec![3]],
+			b"batch".to_vec(),
+			BatchMode::Atomic
+		));
+		assert!(PoeModule::claims_by_owner(1).is_empty());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(PoeModule::revocation(vec![3]).map(|info| info.reason), Some(b"batch".to_vec()));
+	})
+}
+
+#[test]
+fn delegates_can_revoke_and_transfer() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], None));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2], None));
+
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(2), vec![1], vec![]),
+			<Error<Test>>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::add_delegate(Origin::signed(1), 1, None),
+			<Error<Test>>::SelfDelegation
+		);
+		assert_noop!(
+			PoeModule::add_delegate(Origin::signed(1), 2, Some(1)),
+			<Error<Test>>::InvalidExpiry
+		);
+		assert_ok!(PoeModule::add_delegate(Origin::signed(1), 2, Some(10)));
+		System::assert_last_event(Event::PoeModule(crate::Event::DelegateAdded(1, 2, Some(10))));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(2), vec![1], 3));
+		System::assert_last_event(Event::PoeModule(crate::Event::DelegateActed(2, 1, vec![1])));
+		assert_eq!(PoeModule::claim_record(vec![1]).map(|record| record.owner), Some(3));
+
+		// The delegation only covers the claims of the account that made it.
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(2), vec![1], vec![]),
+			<Error<Test>>::NotClaimOwner
+		);
+		// Nor does it let the delegate do anything else with them.
+		assert_noop!(
+			PoeModule::renew_claim(Origin::signed(2), vec![2], Some(20)),
+			<Error<Test>>::NotClaimOwner
+		);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), vec![2], b"lost".to_vec()));
+		System::assert_last_event(Event::PoeModule(crate::Event::DelegateActed(2, 1, vec![2])));
+		let revocation = PoeModule::revocation(vec![2]).unwrap();
+		assert_eq!((revocation.owner, revocation.revoked_by), (1, 2));
+
+		// An expired delegation no longer counts.
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![4], None));
+		System::set_block_number(10);
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(2), vec![4], vec![]),
+			<Error<Test>>::NotClaimOwner
+		);
+
+		// Anyone can clear an expired delegation, returning its deposit to the owner.
+		assert_eq!(Balances::reserved_balance(1), 6);
+		assert_ok!(PoeModule::remove_expired_delegate(Origin::signed(3), 1, 2));
+		System::assert_last_event(Event::PoeModule(crate::Event::DelegateRemoved(1, 2)));
+		assert_eq!(Balances::reserved_balance(1), 1);
+		assert_noop!(
+			PoeModule::remove_delegate(Origin::signed(1), 2),
+			<Error<Test>>::DelegateNotExist
+		);
+
+		// Delegates also offer claims and manage digest claims.
+		assert_ok!(PoeModule::add_delegate(Origin::signed(1), 2, None));
+		assert_noop!(
+			PoeModule::remove_expired_delegate(Origin::signed(3), 1, 2),
+			<Error<Test>>::DelegateNotExpired
+		);
+		assert_ok!(PoeModule::propose_transfer(Origin::signed(2), vec![4], 3));
+		System::assert_last_event(Event::PoeModule(crate::Event::DelegateActed(2, 1, vec![4])));
+		assert_ok!(PoeModule::accept_transfer(Origin::signed(3), vec![4]));
+
+		let algorithm = HashAlgorithm::Blake2_256;
+		let digest = algorithm.hash(b"delegated document");
+		assert_ok!(PoeModule::create_digest_claim(Origin::signed(1), algorithm, digest.clone()));
+		assert_ok!(PoeModule::revoke_digest_claim(
+			Origin::signed(2),
+			algorithm,
+			digest.clone(),
+			vec![]
+		));
+		System::assert_last_event(Event::PoeModule(crate::Event::DelegateActed(2, 1, digest)));
+		assert!(PoeModule::claims_by_owner(1).is_empty());
+
+		assert_ok!(PoeModule::remove_delegate(Origin::signed(1), 2));
+		assert_eq!(Balances::reserved_balance(1), 0);
 	})
 }
@@ -0,0 +1,55 @@
+//! Storage migrations for pallet_template.
+
+use super::*;
+use core::marker::PhantomData;
+use frame_support::{
+	log,
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+
+pub mod v1 {
+	use super::*;
+
+	/// Starts versioning the storage of the pallet. The layout of `Something` is unchanged, so
+	/// this only stamps the version.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain != 0 {
+				log::info!(target: "runtime::template", "skipping v1 migration, on-chain version is {:?}", on_chain);
+				return T::DbWeight::get().reads(1)
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: "runtime::template", "migrated storage to v1");
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			if Pallet::<T>::on_chain_storage_version() < 1 {
+				Self::set_temp_storage(<Something<T>>::get(), "template_v1_something");
+			}
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+			let before: Option<u32> = match Self::get_temp_storage("template_v1_something") {
+				Some(before) => before,
+				None => return Ok(()),
+			};
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 1,
+				"template storage version must be at least 1"
+			);
+			frame_support::ensure!(<Something<T>>::get() == before, "something changed in migration");
+			Ok(())
+		}
+	}
+}
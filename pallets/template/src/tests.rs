@@ -18,3 +18,22 @@ fn correct_error_for_none_value() {
 		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
 	});
 }
+
+#[test]
+fn migration_to_v1_stamps_storage_version() {
+	use crate::migrations::v1::MigrateToV1;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		assert_eq!(TemplateModule::something(), Some(42));
+
+		// Running it again leaves the storage alone.
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+	});
+}
@@ -12,8 +12,9 @@ pub use frame_support::{
 	construct_runtime, parameter_types,
 	StorageValue,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
 	},
+	PalletId,
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
@@ -70,6 +71,9 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// A timestamp: milliseconds since the unix epoch.
+pub type Moment = u64;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -104,12 +108,14 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// The version of the runtime specification. A full node will not attempt to use its native
 	//   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
-	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
-	//   the compatible custom types.
-	spec_version: 100,
+	// This value started at 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
+	//   the compatible custom types. Bump it on every upgrade, `set_code` rejects a runtime
+	//   with the same version and `Migrations` only run when it changes.
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	// Bump whenever call indices or call encodings change.
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -232,8 +238,7 @@ impl pallet_grandpa::Config for Runtime {
 }
 
 impl pallet_timestamp::Config for Runtime {
-	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = ConstU64<{ SLOT_DURATION / 2 }>;
 	type WeightInfo = ();
@@ -241,7 +246,7 @@ impl pallet_timestamp::Config for Runtime {
 
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = ConstU32<50>;
-	type MaxReserves = ();
+	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	/// The type for recording an account's balance.
 	type Balance = Balance;
@@ -272,15 +277,66 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const ClaimDeposit: Balance = 1_000_000;
+	pub const DelegateDeposit: Balance = 10_000_000;
+	/// Direct transfers are disabled on chain; benchmark builds re-enable them so that
+	/// `transfer_claim` and `transfer_digest_claim` are measured on their full path.
+	pub const AllowDirectTransfer: bool = cfg!(feature = "runtime-benchmarks");
+	/// Covers the 32 entries of a claim history, each at most 77 bytes.
+	pub const HistoryDeposit: Balance = 32 * 77 * 1_000_000;
+	pub const OrgDeposit: Balance = 100_000_000;
+	pub const OrgMemberDeposit: Balance = 10_000_000;
+	pub const PoePalletId: PalletId = PalletId(*b"py/proof");
+}
+
 impl pallet_poe::Config for Runtime {
 	type Event = Event;
 	type MaxclaimLength = ConstU32<512>;
 	type WeightInfo = pallet_poe::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type ClaimDeposit = ClaimDeposit;
+	type MaxCosigners = ConstU32<16>;
+	type AllowDirectTransfer = AllowDirectTransfer;
+	type MaxBatchMetadata = ConstU32<256>;
+	type MaxMetadataLength = ConstU32<128>;
+	type MaxHistoryLength = ConstU32<32>;
+	type MaxReasonLength = ConstU32<64>;
+	type AllowRecreateRevoked = ConstBool<false>;
+	type MaxPayloadLength = ConstU32<{ 1024 * 1024 }>;
+	type MaxBatchSize = ConstU32<32>;
+	type DelegateDeposit = DelegateDeposit;
+	type HistoryDeposit = HistoryDeposit;
+	type OrgDeposit = OrgDeposit;
+	type OrgMemberDeposit = OrgMemberDeposit;
+	type MaxOrgMembers = ConstU32<100>;
+	type PalletId = PoePalletId;
+	type TombstoneLifetime = ConstU32<{ 365 * DAYS }>;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<100>;
+	type AssetAccountDeposit = ConstU128<1>;
+	type MetadataDepositBase = ConstU128<10>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
 	pub const MaxKittyOwned: u32 = 9999;
 	pub const ReservationFee: u128 = 100;
+	pub const ReservationFeeStep: u128 = 10;
+	pub const KittyReserveId: [u8; 8] = *b"kitty/dp";
+	pub const KittyPalletId: PalletId = PalletId(*b"py/kitty");
 }
 
 impl pallet_kitty::Config for Runtime {
@@ -289,7 +345,14 @@ impl pallet_kitty::Config for Runtime {
 	type Randomness = RandomnessCollectiveFlip;
 	type MaxKittyOwned = MaxKittyOwned;
 	type ReservableCurrency = Balances;
+	type ReserveId = KittyReserveId;
 	type ReservationFee = ReservationFee;
+	type ReservationFeeStep = ReservationFeeStep;
+	type OnKittyChange = ();
+	type PalletId = KittyPalletId;
+	type ShareAssetId = u32;
+	type ShareAssets = Assets;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
 }
 
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
@@ -364,6 +427,7 @@ construct_runtime!(
 		TemplateModule: pallet_template,
 		PoeModule: pallet_poe,
 		KittyModule: pallet_kitty,
+		Assets: pallet_assets,
 	}
 );
 
@@ -395,8 +459,21 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// Storage migrations run on the next runtime upgrade.
+pub type Migrations = (
+	pallet_kitty::migrations::v1::MigrateToV1<Runtime, ReservationFee>,
+	pallet_kitty::migrations::ReReserveDeposits<Runtime>,
+	pallet_poe::migrations::v1::MigrateToV1<Runtime>,
+	pallet_poe::migrations::v2::MigrateToV2<Runtime>,
+	pallet_poe::migrations::v3::MigrateToV3<Runtime>,
+	pallet_poe::migrations::v4::MigrateToV4<Runtime>,
+	pallet_poe::migrations::v5::MigrateToV5<Runtime>,
+	pallet_template::migrations::v1::MigrateToV1<Runtime>,
+);
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benches {
 	define_benchmarks!(
@@ -540,6 +617,40 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_poe_runtime_api::PoeApi<Block, AccountId, BlockNumber, Moment> for Runtime {
+		fn endorsement_status(claim: Vec<u8>) -> Option<pallet_poe::EndorsementInfo<AccountId>> {
+			PoeModule::endorsement_status(claim)
+		}
+
+		fn verify_inclusion(leaf: sp_core::H256, proof: Vec<sp_core::H256>, root: sp_core::H256) -> bool {
+			PoeModule::verify_inclusion(leaf, proof, root)
+		}
+
+		fn claim(claim: Vec<u8>) -> Option<pallet_poe::ClaimRecord<AccountId, BlockNumber, Moment>> {
+			PoeModule::claim_record(claim)
+		}
+
+		fn claims_by_owner(owner: AccountId) -> Vec<pallet_poe::ClaimRecord<AccountId, BlockNumber, Moment>> {
+			PoeModule::claims_by_owner(owner)
+		}
+
+		fn proof_anchor(claim: Vec<u8>) -> Option<pallet_poe::ProofAnchor<BlockNumber, Moment>> {
+			PoeModule::proof_anchor(claim)
+		}
+
+		fn claim_history(claim: Vec<u8>) -> Vec<pallet_poe::HistoryEntry<AccountId, BlockNumber, Moment>> {
+			PoeModule::claim_history(claim)
+		}
+
+		fn revocation(claim: Vec<u8>) -> Option<pallet_poe::RevocationInfo<AccountId, BlockNumber, Vec<u8>>> {
+			PoeModule::revocation(claim)
+		}
+
+		fn credential(credential: sp_core::H256) -> Option<pallet_poe::CredentialStatus<AccountId, BlockNumber>> {
+			PoeModule::credential_status(credential)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -606,3 +717,56 @@ impl_runtime_apis! {
 		}
 	}
 }
+
+#[cfg(all(test, feature = "try-runtime"))]
+mod tests {
+	use super::*;
+	use frame_support::{
+		storage::{migration::put_storage_value, StorageHasher},
+		traits::{GetStorageVersion, StorageVersion},
+		Blake2_128Concat, BoundedVec,
+	};
+
+	#[test]
+	fn migrations_upgrade_from_v0_with_checks() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			StorageVersion::new(0).put::<KittyModule>();
+			StorageVersion::new(0).put::<PoeModule>();
+			StorageVersion::new(0).put::<TemplateModule>();
+
+			// A v0 claim and template value, left behind by the runtime before versioning.
+			let claim = <BoundedVec<u8, <Runtime as pallet_poe::Config>::MaxclaimLength>>::try_from(
+				vec![1; 32],
+			)
+			.unwrap();
+			put_storage_value(
+				b"PoeModule",
+				b"Proofs",
+				&Blake2_128Concat::hash(&claim.encode()),
+				(AccountId::from([1; 32]), 1 as BlockNumber),
+			);
+			pallet_template::Something::<Runtime>::put(42);
+
+			// Runs every `pre_upgrade`, every migration, then every `post_upgrade`.
+			Executive::try_runtime_upgrade().unwrap();
+
+			assert_eq!(
+				KittyModule::on_chain_storage_version(),
+				KittyModule::current_storage_version()
+			);
+			assert_eq!(
+				PoeModule::on_chain_storage_version(),
+				PoeModule::current_storage_version()
+			);
+			assert_eq!(
+				TemplateModule::on_chain_storage_version(),
+				TemplateModule::current_storage_version()
+			);
+			assert!(PoeModule::claim_record(claim.into_inner()).is_some());
+			assert_eq!(TemplateModule::something(), Some(42));
+
+			// A chain already up to date passes the checks too.
+			Executive::try_runtime_upgrade().unwrap();
+		});
+	}
+}
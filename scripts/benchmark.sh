@@ -0,0 +1,20 @@
+#!/usr/bin/env bash
+# This script is meant to be run on Unix/Linux based systems
+set -e
+
+echo "*** Regenerating pallet_poe weights ***"
+
+cd $(dirname ${BASH_SOURCE[0]})/..
+
+cargo build --release --features runtime-benchmarks
+
+./target/release/node-template benchmark pallet \
+   --chain dev \
+   --execution wasm \
+   --wasm-execution compiled \
+   --pallet pallet_poe \
+   --extrinsic '*' \
+   --steps 20 \
+   --repeat 10 \
+   --output ./pallets/poe/src/weights.rs \
+   --template .maintain/frame-weight-template.hbs